@@ -0,0 +1,27 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rock_paper_scissors::{stream, ErrorPolicy, RpsRound, SecondColumn};
+
+fuzz_target!(|guide: &str| {
+    for decoder in [SecondColumn::Move, SecondColumn::Outcome] {
+        let line_count = guide.lines().count();
+
+        match RpsRound::try_from_lines(guide.lines(), &decoder, ErrorPolicy::CollectAll) {
+            Ok(rounds) => {
+                assert_eq!(line_count, rounds.len());
+
+                // The streaming scorer must agree with the in-memory one
+                let total = u64::from(RpsRound::total_score(rounds));
+                assert_eq!(
+                    total,
+                    stream::score_reader(guide.as_bytes(), decoder).unwrap()
+                );
+            }
+            Err(errors) => {
+                assert!(!errors.is_empty() && errors.len() <= line_count);
+                assert!(errors.windows(2).all(|pair| pair[0].line < pair[1].line));
+            }
+        }
+    }
+});
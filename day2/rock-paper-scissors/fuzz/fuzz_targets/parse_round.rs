@@ -0,0 +1,20 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rock_paper_scissors::{Move, RpsRound, SecondColumn};
+
+fuzz_target!(|line: &str| {
+    let _ = Move::from_char(line);
+
+    for decoder in [SecondColumn::Move, SecondColumn::Outcome] {
+        if let Ok(round) = RpsRound::parse(line, &decoder) {
+            // Every valid round scores between 1 (lose with Rock) and 9 (win with Scissors)
+            assert!((1..=9).contains(&round.score()));
+
+            // Each column only accepts its own codes
+            let mut fields = line.split_whitespace();
+            assert!(matches!(fields.next(), Some("A" | "B" | "C")));
+            assert!(matches!(fields.next(), Some("X" | "Y" | "Z")));
+        }
+    }
+});
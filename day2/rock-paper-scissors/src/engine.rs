@@ -0,0 +1,381 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    sync::OnceLock,
+};
+
+use crate::Outcome;
+
+/// Index of a move within a `Game`
+pub type MoveId = usize;
+
+/// Problems found while defining a game
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum GameError {
+    /// A game needs at least one move
+    NoMoves,
+    /// Balanced cyclic games need an odd number of moves
+    EvenMoveCount(usize),
+    /// Two moves share the same name
+    DuplicateName(String),
+    /// A relation referenced a move that doesn't exist
+    UnknownMove(MoveId),
+    /// A move was said to beat itself
+    SelfBeating(MoveId),
+    /// Both moves of a pair were said to beat each other
+    Contradiction(MoveId, MoveId),
+    /// Nothing decides which of the two moves wins
+    Undecided(MoveId, MoveId),
+    /// The score table doesn't have one entry per move
+    ScoreCount { expected: usize, found: usize },
+}
+
+impl Display for GameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            GameError::NoMoves => write!(f, "a game needs at least one move"),
+            GameError::EvenMoveCount(n) => {
+                write!(f, "a balanced game needs an odd number of moves, found {n}")
+            }
+            GameError::DuplicateName(name) => write!(f, "move '{name}' is defined twice"),
+            GameError::UnknownMove(id) => write!(f, "move {id} doesn't exist"),
+            GameError::SelfBeating(id) => write!(f, "move {id} can't beat itself"),
+            GameError::Contradiction(a, b) => write!(f, "moves {a} and {b} both beat each other"),
+            GameError::Undecided(a, b) => write!(f, "no winner between moves {a} and {b}"),
+            GameError::ScoreCount { expected, found } => {
+                write!(f, "expected {expected} move scores, found {found}")
+            }
+        }
+    }
+}
+
+impl Error for GameError {}
+
+/// Points awarded for each outcome of a round
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct OutcomeScores {
+    pub win: u32,
+    pub draw: u32,
+    pub lose: u32,
+}
+
+impl OutcomeScores {
+    pub fn score(&self, outcome: &Outcome) -> u32 {
+        match outcome {
+            Outcome::Win => self.win,
+            Outcome::Draw => self.draw,
+            Outcome::Lose => self.lose,
+        }
+    }
+}
+
+impl Default for OutcomeScores {
+    fn default() -> Self {
+        OutcomeScores {
+            win: 6,
+            draw: 3,
+            lose: 0,
+        }
+    }
+}
+
+/// A simultaneous-move game where every pair of distinct moves has a winner
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Game {
+    names: Vec<String>,
+    /// `beats[a][b]` is true when move `a` beats move `b`
+    beats: Vec<Vec<bool>>,
+    move_scores: Vec<u32>,
+    outcome_scores: OutcomeScores,
+}
+
+impl Game {
+    /// Create a game from explicit `(winner, loser)` pairs.
+    ///
+    /// Every pair of distinct moves must be decided exactly once. Moves score 1, 2, 3... in the
+    /// order they are named.
+    pub fn from_relation(names: &[&str], wins: &[(MoveId, MoveId)]) -> Result<Self, GameError> {
+        let count = Game::check_names(names)?;
+
+        let mut beats = vec![vec![false; count]; count];
+        for &(winner, loser) in wins {
+            if winner >= count {
+                return Err(GameError::UnknownMove(winner));
+            }
+            if loser >= count {
+                return Err(GameError::UnknownMove(loser));
+            }
+            if winner == loser {
+                return Err(GameError::SelfBeating(winner));
+            }
+            if beats[loser][winner] {
+                return Err(GameError::Contradiction(winner, loser));
+            }
+            beats[winner][loser] = true;
+        }
+
+        for (a, row) in beats.iter().enumerate() {
+            if let Some(b) = ((a + 1)..count).find(|&b| !row[b] && !beats[b][a]) {
+                return Err(GameError::Undecided(a, b));
+            }
+        }
+
+        Ok(Game::with_default_scores(names, beats))
+    }
+
+    /// Create a balanced cyclic game where every move beats exactly half of the others.
+    ///
+    /// Move `a` beats move `b` when `a` comes an odd number of places after `b` (wrapping
+    /// around), so the order of `names` decides the relation.
+    pub fn balanced(names: &[&str]) -> Result<Self, GameError> {
+        let count = Game::check_names(names)?;
+        if count % 2 == 0 {
+            return Err(GameError::EvenMoveCount(count));
+        }
+
+        let beats = (0..count)
+            .map(|a| {
+                (0..count)
+                    .map(|b| (a + count - b) % count % 2 == 1)
+                    .collect()
+            })
+            .collect();
+
+        Ok(Game::with_default_scores(names, beats))
+    }
+
+    /// Plain Rock-Paper-Scissors
+    pub fn rps() -> Self {
+        Game::balanced(&["Rock", "Paper", "Scissors"]).unwrap()
+    }
+
+    /// Rock-Paper-Scissors-Lizard-Spock
+    pub fn rpsls() -> Self {
+        Game::balanced(&["Rock", "Paper", "Scissors", "Spock", "Lizard"]).unwrap()
+    }
+
+    /// Replace the points awarded for playing each move
+    pub fn with_move_scores(mut self, move_scores: Vec<u32>) -> Result<Self, GameError> {
+        if move_scores.len() != self.names.len() {
+            return Err(GameError::ScoreCount {
+                expected: self.names.len(),
+                found: move_scores.len(),
+            });
+        }
+
+        self.move_scores = move_scores;
+        Ok(self)
+    }
+
+    /// Replace the points awarded for each outcome
+    pub fn with_outcome_scores(mut self, outcome_scores: OutcomeScores) -> Self {
+        self.outcome_scores = outcome_scores;
+        self
+    }
+
+    fn check_names(names: &[&str]) -> Result<usize, GameError> {
+        if names.is_empty() {
+            return Err(GameError::NoMoves);
+        }
+
+        for (i, name) in names.iter().enumerate() {
+            if names[..i].contains(name) {
+                return Err(GameError::DuplicateName(name.to_string()));
+            }
+        }
+
+        Ok(names.len())
+    }
+
+    fn with_default_scores(names: &[&str], beats: Vec<Vec<bool>>) -> Self {
+        Game {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            beats,
+            move_scores: (1..=names.len() as u32).collect(),
+            outcome_scores: OutcomeScores::default(),
+        }
+    }
+
+    pub fn move_count(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn name(&self, id: MoveId) -> &str {
+        &self.names[id]
+    }
+
+    /// Look up a move by its (case-sensitive) name
+    pub fn move_id(&self, name: &str) -> Option<MoveId> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    pub fn beats(&self, this_move: MoveId, op_move: MoveId) -> bool {
+        self.beats[this_move][op_move]
+    }
+
+    /// The outcome of playing `this_move` against `op_move`
+    pub fn outcome(&self, this_move: MoveId, op_move: MoveId) -> Outcome {
+        if this_move == op_move {
+            Outcome::Draw
+        } else if self.beats(this_move, op_move) {
+            Outcome::Win
+        } else {
+            Outcome::Lose
+        }
+    }
+
+    /// Every move that achieves `outcome` against `op_move`, in move order
+    pub fn moves_for(&self, op_move: MoveId, outcome: &Outcome) -> Vec<MoveId> {
+        (0..self.move_count())
+            .filter(|&this_move| self.outcome(this_move, op_move) == *outcome)
+            .collect()
+    }
+
+    /// The highest scoring move that achieves `outcome` against `op_move`, if any does
+    pub fn best_move_for(&self, op_move: MoveId, outcome: &Outcome) -> Option<MoveId> {
+        self.moves_for(op_move, outcome)
+            .into_iter()
+            .rev()
+            .max_by_key(|&this_move| self.move_score(this_move))
+    }
+
+    pub fn move_score(&self, id: MoveId) -> u32 {
+        self.move_scores[id]
+    }
+
+    pub fn outcome_score(&self, outcome: &Outcome) -> u32 {
+        self.outcome_scores.score(outcome)
+    }
+
+    /// Total points for playing `this_move` against `op_move`
+    pub fn score(&self, this_move: MoveId, op_move: MoveId) -> u32 {
+        self.move_score(this_move) + self.outcome_score(&self.outcome(this_move, op_move))
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Game::rps()
+    }
+}
+
+/// Shared Rock-Paper-Scissors preset backing `Move` and `Outcome`
+pub(crate) fn rps() -> &'static Game {
+    static RPS: OnceLock<Game> = OnceLock::new();
+    RPS.get_or_init(Game::rps)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rps_relation() {
+        let game = Game::rps();
+        let rock = game.move_id("Rock").unwrap();
+        let paper = game.move_id("Paper").unwrap();
+        let scissors = game.move_id("Scissors").unwrap();
+
+        assert!(game.beats(paper, rock));
+        assert!(game.beats(scissors, paper));
+        assert!(game.beats(rock, scissors));
+        assert_eq!(Outcome::Draw, game.outcome(rock, rock));
+        assert_eq!(8, game.score(paper, rock));
+    }
+
+    #[test]
+    fn rpsls_relation() {
+        let game = Game::rpsls();
+        let test_data = [
+            ("Scissors", "Paper"),
+            ("Paper", "Rock"),
+            ("Rock", "Lizard"),
+            ("Lizard", "Spock"),
+            ("Spock", "Scissors"),
+            ("Scissors", "Lizard"),
+            ("Lizard", "Paper"),
+            ("Paper", "Spock"),
+            ("Spock", "Rock"),
+            ("Rock", "Scissors"),
+        ];
+
+        for data in test_data {
+            let winner = game.move_id(data.0).unwrap();
+            let loser = game.move_id(data.1).unwrap();
+            assert_eq!(Outcome::Win, game.outcome(winner, loser));
+            assert_eq!(Outcome::Lose, game.outcome(loser, winner));
+        }
+    }
+
+    #[test]
+    fn balanced_games_are_balanced() {
+        for count in [1, 3, 5, 7, 9] {
+            let names: Vec<String> = (0..count).map(|i| i.to_string()).collect();
+            let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+            let game = Game::balanced(&names).unwrap();
+
+            for op_move in 0..count {
+                assert_eq!(
+                    (count - 1) / 2,
+                    game.moves_for(op_move, &Outcome::Win).len()
+                );
+                assert_eq!(
+                    (count - 1) / 2,
+                    game.moves_for(op_move, &Outcome::Lose).len()
+                );
+                assert_eq!(vec![op_move], game.moves_for(op_move, &Outcome::Draw));
+            }
+        }
+
+        assert_eq!(
+            Err(GameError::EvenMoveCount(4)),
+            Game::balanced(&["a", "b", "c", "d"])
+        );
+    }
+
+    #[test]
+    fn relation_validation() {
+        let names = ["a", "b", "c"];
+        let test_data = [
+            (vec![(0, 1), (1, 2), (2, 0)], Ok(())),
+            (vec![(0, 1), (1, 2)], Err(GameError::Undecided(0, 2))),
+            (vec![(0, 1), (1, 0)], Err(GameError::Contradiction(1, 0))),
+            (vec![(1, 1)], Err(GameError::SelfBeating(1))),
+            (vec![(0, 3)], Err(GameError::UnknownMove(3))),
+        ];
+
+        for data in test_data {
+            assert_eq!(data.1, Game::from_relation(&names, &data.0).map(|_| ()));
+        }
+
+        assert_eq!(
+            Err(GameError::DuplicateName("a".to_string())),
+            Game::from_relation(&["a", "a"], &[(0, 1)])
+        );
+    }
+
+    #[test]
+    fn best_move_uses_score_table() {
+        let game = Game::rpsls();
+        let rock = game.move_id("Rock").unwrap();
+
+        // Paper and Spock both beat Rock, Spock is worth more by default
+        assert_eq!(
+            game.move_id("Spock"),
+            game.best_move_for(rock, &Outcome::Win)
+        );
+
+        let game = game.with_move_scores(vec![1, 10, 3, 4, 5]).unwrap();
+        assert_eq!(
+            game.move_id("Paper"),
+            game.best_move_for(rock, &Outcome::Win)
+        );
+
+        let game = game.with_outcome_scores(OutcomeScores {
+            win: 2,
+            draw: 1,
+            lose: 0,
+        });
+        assert_eq!(12, game.score(1, rock));
+    }
+}
@@ -0,0 +1,281 @@
+use std::{
+    fmt::{Display, Formatter},
+    io::{self, Write},
+};
+
+use crate::{Move, Outcome, RpsRound};
+
+const OUTCOMES: [Outcome; 3] = [Outcome::Win, Outcome::Draw, Outcome::Lose];
+
+fn outcome_index(outcome: &Outcome) -> usize {
+    match outcome {
+        Outcome::Win => 0,
+        Outcome::Draw => 1,
+        Outcome::Lose => 2,
+    }
+}
+
+/// How traced rounds are written out
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TraceFormat {
+    /// Aligned, human-readable text
+    Text,
+    /// One JSON object per line, suitable for diffing runs
+    JsonLines,
+}
+
+/// Running counts over every traced round
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Summary {
+    pub rounds: u64,
+    /// Opponent move counts, indexed by `Move::id`
+    pub opponent_moves: [u64; 3],
+    /// Chosen move counts, indexed by `Move::id`
+    pub chosen_moves: [u64; 3],
+    /// Win, draw and loss counts, in that order
+    pub outcomes: [u64; 3],
+    pub total_score: u64,
+}
+
+impl Summary {
+    pub fn add(&mut self, round: &RpsRound) {
+        self.rounds += 1;
+        self.opponent_moves[round.opponent_move().id()] += 1;
+        self.chosen_moves[round.recommended_move().id()] += 1;
+        self.outcomes[outcome_index(&round.outcome())] += 1;
+        self.total_score += u64::from(round.score());
+    }
+
+    pub fn outcome_count(&self, outcome: &Outcome) -> u64 {
+        self.outcomes[outcome_index(outcome)]
+    }
+
+    /// The summary as a single JSON object
+    pub fn to_json(&self) -> String {
+        let moves = |counts: &[u64; 3]| {
+            Move::ALL
+                .iter()
+                .map(|m| format!("\"{m}\":{}", counts[m.id()]))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let outcomes = OUTCOMES
+            .iter()
+            .map(|o| format!("\"{o}\":{}", self.outcome_count(o)))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!(
+            "{{\"rounds\":{},\"opponent_moves\":{{{}}},\"chosen_moves\":{{{}}},\"outcomes\":{{{}}},\"total_score\":{}}}",
+            self.rounds,
+            moves(&self.opponent_moves),
+            moves(&self.chosen_moves),
+            outcomes,
+            self.total_score
+        )
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        writeln!(f, "{:<10}{:>10}{:>10}", "Move", "Opponent", "Chosen")?;
+        for m in Move::ALL {
+            writeln!(
+                f,
+                "{:<10}{:>10}{:>10}",
+                m,
+                self.opponent_moves[m.id()],
+                self.chosen_moves[m.id()]
+            )?;
+        }
+        writeln!(f)?;
+
+        writeln!(f, "{:<10}{:>10}", "Outcome", "Count")?;
+        for o in OUTCOMES {
+            writeln!(f, "{:<10}{:>10}", o, self.outcome_count(&o))?;
+        }
+        writeln!(f)?;
+
+        writeln!(f, "Rounds: {}", self.rounds)?;
+        write!(f, "Total score: {}", self.total_score)
+    }
+}
+
+/// Writes a breakdown of every round it's given, followed by a summary
+pub struct Tracer<W> {
+    out: W,
+    format: TraceFormat,
+    summary: Summary,
+    show_rounds: bool,
+    show_summary: bool,
+}
+
+impl<W: Write> Tracer<W> {
+    pub fn new(out: W, format: TraceFormat) -> Self {
+        Tracer {
+            out,
+            format,
+            summary: Summary::default(),
+            show_rounds: true,
+            show_summary: true,
+        }
+    }
+
+    /// Choose whether each round's breakdown is written
+    pub fn show_rounds(mut self, show_rounds: bool) -> Self {
+        self.show_rounds = show_rounds;
+        self
+    }
+
+    /// Choose whether the summary is written by `finish`
+    pub fn show_summary(mut self, show_summary: bool) -> Self {
+        self.show_summary = show_summary;
+        self
+    }
+
+    pub fn summary(&self) -> &Summary {
+        &self.summary
+    }
+
+    /// Record `round` and write out its breakdown
+    pub fn round(&mut self, round: &RpsRound) -> io::Result<()> {
+        self.summary.add(round);
+        let number = self.summary.rounds;
+        if !self.show_rounds {
+            return Ok(());
+        }
+
+        match self.format {
+            TraceFormat::Text => writeln!(
+                self.out,
+                "{:>6}  {:<8} vs {:<8} {:<4}  {} + {} = {}  (total {})",
+                number,
+                round.recommended_move(),
+                round.opponent_move(),
+                round.outcome(),
+                round.recommended_move().score(),
+                round.outcome().score(),
+                round.score(),
+                self.summary.total_score
+            ),
+            TraceFormat::JsonLines => writeln!(
+                self.out,
+                "{{\"round\":{},\"opponent\":\"{}\",\"chosen\":\"{}\",\"outcome\":\"{}\",\"move_score\":{},\"outcome_score\":{},\"score\":{},\"total\":{}}}",
+                number,
+                round.opponent_move(),
+                round.recommended_move(),
+                round.outcome(),
+                round.recommended_move().score(),
+                round.outcome().score(),
+                round.score(),
+                self.summary.total_score
+            ),
+        }
+    }
+
+    /// Write the summary and hand it back
+    pub fn finish(mut self) -> io::Result<Summary> {
+        match (self.show_summary, self.format) {
+            (false, _) => {}
+            (true, TraceFormat::Text) if self.show_rounds => {
+                writeln!(self.out, "\n{}", self.summary)?
+            }
+            (true, TraceFormat::Text) => writeln!(self.out, "{}", self.summary)?,
+            (true, TraceFormat::JsonLines) => {
+                writeln!(self.out, "{{\"summary\":{}}}", self.summary.to_json())?
+            }
+        }
+        self.out.flush()?;
+
+        Ok(self.summary)
+    }
+}
+
+/// Trace every round in `rounds` to `out`
+pub fn explain<'a>(
+    rounds: impl IntoIterator<Item = &'a RpsRound>,
+    out: impl Write,
+    format: TraceFormat,
+) -> io::Result<Summary> {
+    let mut tracer = Tracer::new(out, format);
+    for round in rounds {
+        tracer.round(round)?;
+    }
+
+    tracer.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SecondColumn;
+
+    fn rounds() -> Vec<RpsRound> {
+        RpsRound::from_lines("A Y\nB X\nC Z".lines(), &SecondColumn::Move)
+    }
+
+    #[test]
+    fn summary_counts() {
+        let mut summary = Summary::default();
+        for round in rounds() {
+            summary.add(&round);
+        }
+
+        assert_eq!(3, summary.rounds);
+        assert_eq!([1, 1, 1], summary.opponent_moves);
+        assert_eq!([1, 1, 1], summary.chosen_moves);
+        assert_eq!(1, summary.outcome_count(&Outcome::Win));
+        assert_eq!(1, summary.outcome_count(&Outcome::Lose));
+        assert_eq!(15, summary.total_score);
+    }
+
+    #[test]
+    fn text_trace() {
+        let mut out = Vec::new();
+        explain(&rounds(), &mut out, TraceFormat::Text).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(
+            "     1  Paper    vs Rock     Win   2 + 6 = 8  (total 8)",
+            lines[0]
+        );
+        assert_eq!(
+            "     3  Scissors vs Scissors Draw  3 + 3 = 6  (total 15)",
+            lines[2]
+        );
+        assert_eq!(Some(&"Total score: 15"), lines.last());
+    }
+
+    #[test]
+    fn json_lines_trace() {
+        let mut out = Vec::new();
+        explain(&rounds(), &mut out, TraceFormat::JsonLines).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(4, lines.len());
+        assert_eq!(
+            "{\"round\":2,\"opponent\":\"Paper\",\"chosen\":\"Rock\",\"outcome\":\"Lose\",\"move_score\":1,\"outcome_score\":0,\"score\":1,\"total\":9}",
+            lines[1]
+        );
+        assert_eq!(
+            "{\"summary\":{\"rounds\":3,\"opponent_moves\":{\"Rock\":1,\"Paper\":1,\"Scissors\":1},\"chosen_moves\":{\"Rock\":1,\"Paper\":1,\"Scissors\":1},\"outcomes\":{\"Win\":1,\"Draw\":1,\"Lose\":1},\"total_score\":15}}",
+            lines[3]
+        );
+    }
+
+    #[test]
+    fn summary_only() {
+        let mut out = Vec::new();
+        let mut tracer = Tracer::new(&mut out, TraceFormat::Text).show_rounds(false);
+        for round in &rounds() {
+            tracer.round(round).unwrap();
+        }
+        let summary = tracer.finish().unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert_eq!(format!("{summary}\n"), out);
+        assert!(out.starts_with("Move"));
+    }
+}
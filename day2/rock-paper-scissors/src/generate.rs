@@ -0,0 +1,248 @@
+use crate::rng::SplitMix64;
+
+const OPPONENT_CODES: [&str; 3] = ["A", "B", "C"];
+const SECOND_CODES: [&str; 3] = ["X", "Y", "Z"];
+/// Codes that aren't valid in either column
+const BAD_CODES: [&str; 6] = ["D", "W", "a", "x", "AX", "é"];
+
+/// Score of every opponent/second column pair when the second column is a move
+const MOVE_SCORES: [[u64; 3]; 3] = [[4, 8, 3], [1, 5, 9], [7, 2, 6]];
+/// Score of every opponent/second column pair when the second column is an outcome
+const OUTCOME_SCORES: [[u64; 3]; 3] = [[3, 4, 8], [1, 5, 9], [2, 6, 7]];
+
+/// Shape of the strategy guide to generate
+#[derive(Debug, PartialEq, Clone)]
+pub struct GuideConfig {
+    /// Number of lines to emit, including malformed ones
+    pub rounds: usize,
+    /// Relative frequency of A, B and C in the first column
+    pub opponent_weights: [u32; 3],
+    /// Relative frequency of X, Y and Z in the second column
+    pub second_weights: [u32; 3],
+    /// Fraction of lines, between 0 and 1, that are deliberately malformed
+    pub bad_line_rate: f64,
+    /// Pad some lines with trailing spaces and tabs
+    pub trailing_whitespace: bool,
+    /// End lines with `\r\n` instead of `\n`
+    pub crlf: bool,
+    pub seed: u64,
+}
+
+impl Default for GuideConfig {
+    fn default() -> Self {
+        GuideConfig {
+            rounds: 100,
+            opponent_weights: [1, 1, 1],
+            second_weights: [1, 1, 1],
+            bad_line_rate: 0.0,
+            trailing_whitespace: false,
+            crlf: false,
+            seed: 0,
+        }
+    }
+}
+
+/// A generated guide along with the answers it should produce
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GeneratedGuide {
+    pub text: String,
+    /// 1-based numbers of the malformed lines
+    pub bad_lines: Vec<usize>,
+    /// Total of the valid lines when the second column is a move
+    pub expected_move_total: u64,
+    /// Total of the valid lines when the second column is an outcome
+    pub expected_outcome_total: u64,
+}
+
+/// Build a strategy guide from `config`.
+///
+/// Expected totals come from fixed lookup tables rather than from `RpsRound`, so they can be
+/// used to check the parser and scorer.
+pub fn generate(config: &GuideConfig) -> GeneratedGuide {
+    let mut rng = SplitMix64::new(config.seed);
+    let line_ending = if config.crlf { "\r\n" } else { "\n" };
+
+    let mut guide = GeneratedGuide {
+        text: String::new(),
+        bad_lines: Vec::new(),
+        expected_move_total: 0,
+        expected_outcome_total: 0,
+    };
+
+    for line in 1..=config.rounds {
+        let op = rng.weighted(&config.opponent_weights);
+        let second = rng.weighted(&config.second_weights);
+
+        if rng.chance(config.bad_line_rate) {
+            let bad_code = BAD_CODES[rng.below(BAD_CODES.len() as u64) as usize];
+            let bad_line = match rng.below(7) {
+                0 => format!("{} {}", bad_code, SECOND_CODES[second]),
+                1 => format!("{} {}", OPPONENT_CODES[op], bad_code),
+                // Codes that are only valid in the other column
+                2 => format!("{} {}", SECOND_CODES[op], SECOND_CODES[second]),
+                3 => format!("{} {}", OPPONENT_CODES[op], OPPONENT_CODES[second]),
+                4 => OPPONENT_CODES[op].to_string(),
+                5 => format!(
+                    "{} {} {}",
+                    OPPONENT_CODES[op], SECOND_CODES[second], bad_code
+                ),
+                _ => String::new(),
+            };
+
+            guide.text.push_str(&bad_line);
+            guide.bad_lines.push(line);
+        } else {
+            guide.text.push_str(OPPONENT_CODES[op]);
+            guide.text.push(' ');
+            guide.text.push_str(SECOND_CODES[second]);
+
+            guide.expected_move_total += MOVE_SCORES[op][second];
+            guide.expected_outcome_total += OUTCOME_SCORES[op][second];
+        }
+
+        if config.trailing_whitespace && rng.chance(0.5) {
+            guide
+                .text
+                .push_str([" ", "  ", "\t", " \t"][rng.below(4) as usize]);
+        }
+        guide.text.push_str(line_ending);
+    }
+
+    guide
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{stream, ErrorPolicy, RpsRound, SecondColumn};
+
+    #[test]
+    fn small_guide_tables() {
+        let config = GuideConfig {
+            rounds: 50,
+            ..GuideConfig::default()
+        };
+        let guide = generate(&config);
+
+        for (decoder, expected) in [
+            (SecondColumn::Move, guide.expected_move_total),
+            (SecondColumn::Outcome, guide.expected_outcome_total),
+        ] {
+            let rounds = RpsRound::from_lines(guide.text.lines(), &decoder);
+            assert_eq!(50, rounds.len());
+            assert_eq!(expected, u64::from(RpsRound::total_score(rounds)));
+        }
+    }
+
+    #[test]
+    fn skewed_weights() {
+        let config = GuideConfig {
+            rounds: 20,
+            opponent_weights: [0, 1, 0],
+            second_weights: [0, 0, 1],
+            ..GuideConfig::default()
+        };
+        let guide = generate(&config);
+
+        assert_eq!("B Z\n".repeat(20), guide.text);
+        assert_eq!(180, guide.expected_move_total);
+    }
+
+    fn weights() -> impl Strategy<Value = [u32; 3]> {
+        prop::array::uniform3(0u32..4).prop_filter("needs a non-zero weight", |weights| {
+            weights.iter().any(|&weight| weight > 0)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn valid_guides_score_as_expected(
+            rounds in 0usize..40,
+            opponent_weights in weights(),
+            second_weights in weights(),
+            trailing_whitespace: bool,
+            crlf: bool,
+            seed: u64,
+        ) {
+            let config = GuideConfig {
+                rounds,
+                opponent_weights,
+                second_weights,
+                trailing_whitespace,
+                crlf,
+                seed,
+                ..GuideConfig::default()
+            };
+            let guide = generate(&config);
+            prop_assert!(guide.bad_lines.is_empty());
+
+            let rounds = RpsRound::from_lines(guide.text.lines(), &SecondColumn::Move);
+            prop_assert_eq!(
+                guide.expected_move_total,
+                u64::from(RpsRound::total_score(rounds))
+            );
+            prop_assert_eq!(
+                guide.expected_outcome_total,
+                stream::score_reader(guide.text.as_bytes(), SecondColumn::Outcome).unwrap()
+            );
+        }
+
+        #[test]
+        fn malformed_lines_are_all_reported(
+            bad_line_rate in 0.0f64..=1.0,
+            trailing_whitespace: bool,
+            crlf: bool,
+            seed: u64,
+        ) {
+            let config = GuideConfig {
+                rounds: 40,
+                bad_line_rate,
+                trailing_whitespace,
+                crlf,
+                seed,
+                ..GuideConfig::default()
+            };
+            let guide = generate(&config);
+
+            for decoder in [SecondColumn::Move, SecondColumn::Outcome] {
+                let errors = match RpsRound::try_from_lines(
+                    guide.text.lines(),
+                    &decoder,
+                    ErrorPolicy::CollectAll,
+                ) {
+                    Ok(_) => Vec::new(),
+                    Err(errors) => errors.iter().map(|err| err.line).collect(),
+                };
+                prop_assert_eq!(&guide.bad_lines, &errors);
+            }
+
+            let valid_total: u32 = guide
+                .text
+                .lines()
+                .filter_map(|line| RpsRound::parse(line, &SecondColumn::Move).ok())
+                .map(|round| round.score())
+                .sum();
+            prop_assert_eq!(guide.expected_move_total, u64::from(valid_total));
+        }
+    }
+
+    #[test]
+    fn cross_column_codes_are_malformed() {
+        let config = GuideConfig {
+            rounds: 500,
+            bad_line_rate: 1.0,
+            ..GuideConfig::default()
+        };
+        let guide = generate(&config);
+
+        let swapped = |line: &str| {
+            let fields: Vec<&str> = line.split(' ').collect();
+            fields.len() == 2
+                && (SECOND_CODES.contains(&fields[0]) || OPPONENT_CODES.contains(&fields[1]))
+        };
+        assert!(guide.text.lines().any(swapped));
+        assert_eq!((1..=500).collect::<Vec<_>>(), guide.bad_lines);
+    }
+}
@@ -0,0 +1,603 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    str::{FromStr, Lines},
+};
+
+pub mod engine;
+pub mod explain;
+pub mod generate;
+mod rng;
+pub mod sim;
+pub mod solver;
+pub mod stream;
+
+/// The kinds of problems that can be found while parsing a round
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseRoundErrorKind {
+    /// The line contained no fields at all
+    EmptyLine,
+    /// The line did not contain exactly two fields
+    FieldCount(usize),
+    /// A field did not match any known move code
+    UnknownMove(String),
+    /// A field did not match any known outcome code
+    UnknownOutcome(String),
+}
+
+/// Error produced when a strategy guide line can't be parsed into an RpsRound
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseRoundError {
+    /// 1-based line number of the offending line
+    pub line: usize,
+    /// 1-based character column where the problem was found
+    pub column: usize,
+    pub kind: ParseRoundErrorKind,
+}
+
+impl ParseRoundError {
+    fn new(column: usize, kind: ParseRoundErrorKind) -> Self {
+        ParseRoundError {
+            line: 1,
+            column,
+            kind,
+        }
+    }
+
+    pub(crate) fn at_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+}
+
+impl Display for ParseRoundError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+
+        match &self.kind {
+            ParseRoundErrorKind::EmptyLine => write!(f, "empty line"),
+            ParseRoundErrorKind::FieldCount(n) => write!(f, "expected 2 fields, found {n}"),
+            ParseRoundErrorKind::UnknownMove(code) => write!(f, "unknown move code '{code}'"),
+            ParseRoundErrorKind::UnknownOutcome(code) => {
+                write!(f, "unknown outcome code '{code}'")
+            }
+        }
+    }
+}
+
+impl Error for ParseRoundError {}
+
+/// How multi-line parsing should react to bad lines
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ErrorPolicy {
+    /// Stop at the first bad line
+    FailFast,
+    /// Keep going and report every bad line
+    CollectAll,
+}
+
+/// Split a round line into its two whitespace-separated fields along with their 1-based columns
+fn round_fields(round_str: &str) -> Result<[(usize, &str); 2], ParseRoundError> {
+    let mut fields = Vec::new();
+    let mut field_start = None;
+
+    for (col, (idx, c)) in round_str.char_indices().enumerate() {
+        match (c.is_whitespace(), field_start) {
+            (false, None) => field_start = Some((col + 1, idx)),
+            (true, Some((start_col, start_idx))) => {
+                fields.push((start_col, &round_str[start_idx..idx]));
+                field_start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((start_col, start_idx)) = field_start {
+        fields.push((start_col, &round_str[start_idx..]));
+    }
+
+    match fields.len() {
+        0 => Err(ParseRoundError::new(1, ParseRoundErrorKind::EmptyLine)),
+        2 => Ok([fields[0], fields[1]]),
+        n => {
+            let column = fields.get(2).map_or(fields[0].0, |field| field.0);
+            Err(ParseRoundError::new(
+                column,
+                ParseRoundErrorKind::FieldCount(n),
+            ))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Move {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let name = match self {
+            Move::Rock => "Rock",
+            Move::Paper => "Paper",
+            Move::Scissors => "Scissors",
+        };
+
+        f.pad(name)
+    }
+}
+
+impl Move {
+    /// Every move, ordered by `id`
+    pub const ALL: [Move; 3] = [Move::Rock, Move::Paper, Move::Scissors];
+
+    /// Decode the opponent's column, which only uses A, B and C
+    pub fn from_char(move_char: &str) -> Option<Self> {
+        match move_char {
+            "A" => Some(Move::Rock),
+            "B" => Some(Move::Paper),
+            "C" => Some(Move::Scissors),
+            _ => None,
+        }
+    }
+
+    /// Decode a part 1 response, which only uses X, Y and Z
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "X" => Some(Move::Rock),
+            "Y" => Some(Move::Paper),
+            "Z" => Some(Move::Scissors),
+            _ => None,
+        }
+    }
+
+    /// Position of the move within the `engine::Game::rps` preset
+    pub fn id(&self) -> engine::MoveId {
+        match self {
+            Move::Rock => 0,
+            Move::Paper => 1,
+            Move::Scissors => 2,
+        }
+    }
+
+    pub fn from_id(id: engine::MoveId) -> Option<Self> {
+        match id {
+            0 => Some(Move::Rock),
+            1 => Some(Move::Paper),
+            2 => Some(Move::Scissors),
+            _ => None,
+        }
+    }
+
+    /// Get the appropriate move for the intended outcome
+    pub fn from_outcome(op_move: &Self, outcome: &Outcome) -> Self {
+        // Every outcome is reachable by exactly one move in plain RPS
+        let id = engine::rps().moves_for(op_move.id(), outcome)[0];
+        Move::from_id(id).unwrap()
+    }
+
+    pub fn score(&self) -> u32 {
+        match self {
+            Move::Rock => 1,
+            Move::Paper => 2,
+            Move::Scissors => 3,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Outcome {
+    Win,
+    Lose,
+    Draw,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let name = match self {
+            Outcome::Win => "Win",
+            Outcome::Lose => "Lose",
+            Outcome::Draw => "Draw",
+        };
+
+        f.pad(name)
+    }
+}
+
+impl Outcome {
+    pub fn from(this_move: &Move, op_move: &Move) -> Self {
+        engine::rps().outcome(this_move.id(), op_move.id())
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "X" => Some(Outcome::Lose),
+            "Y" => Some(Outcome::Draw),
+            "Z" => Some(Outcome::Win),
+            _ => None,
+        }
+    }
+
+    pub fn score(&self) -> u32 {
+        match self {
+            Outcome::Win => 6,
+            Outcome::Lose => 0,
+            Outcome::Draw => 3,
+        }
+    }
+}
+
+/// Decides what the second column of a strategy guide line means.
+///
+/// Implement this to support interpretations beyond the ones offered by `SecondColumn`.
+pub trait ColumnDecoder {
+    /// Decode `code` into the move that should be played against `op_move`
+    fn decode(&self, op_move: &Move, code: &str) -> Result<Move, ParseRoundErrorKind>;
+}
+
+/// The built-in interpretations of the second strategy guide column
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum SecondColumn {
+    /// The column is the move to play, coded X, Y or Z (part 1)
+    #[default]
+    Move,
+    /// The column is the outcome to aim for, coded X, Y or Z (part 2)
+    Outcome,
+}
+
+impl ColumnDecoder for SecondColumn {
+    fn decode(&self, op_move: &Move, code: &str) -> Result<Move, ParseRoundErrorKind> {
+        match self {
+            SecondColumn::Move => {
+                Move::from_code(code).ok_or_else(|| ParseRoundErrorKind::UnknownMove(code.into()))
+            }
+            SecondColumn::Outcome => match Outcome::from_code(code) {
+                Some(outcome) => Ok(Move::from_outcome(op_move, &outcome)),
+                None => Err(ParseRoundErrorKind::UnknownOutcome(code.into())),
+            },
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RpsRound {
+    opponent_move: Move,
+    recommended_move: Move,
+    outcome: Outcome,
+}
+
+impl TryFrom<&str> for RpsRound {
+    type Error = ParseRoundError;
+
+    fn try_from(round_str: &str) -> Result<Self, Self::Error> {
+        RpsRound::parse(round_str, &SecondColumn::default())
+    }
+}
+
+impl FromStr for RpsRound {
+    type Err = ParseRoundError;
+
+    fn from_str(round_str: &str) -> Result<Self, Self::Err> {
+        RpsRound::try_from(round_str)
+    }
+}
+
+impl Display for RpsRound {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "{} vs {}: {} ({} + {} = {})",
+            self.recommended_move,
+            self.opponent_move,
+            self.outcome,
+            self.recommended_move.score(),
+            self.outcome.score(),
+            self.score()
+        )
+    }
+}
+
+impl RpsRound {
+    /// Create a round from the moves played by each side
+    pub fn new(opponent_move: Move, recommended_move: Move) -> Self {
+        RpsRound {
+            opponent_move,
+            recommended_move,
+            outcome: Outcome::from(&recommended_move, &opponent_move),
+        }
+    }
+
+    /// Parse a single line, using `decoder` to interpret the second column
+    pub fn parse(round_str: &str, decoder: &impl ColumnDecoder) -> Result<Self, ParseRoundError> {
+        let [op_field, second_field] = round_fields(round_str)?;
+
+        let op_move = Move::from_char(op_field.1).ok_or_else(|| {
+            ParseRoundError::new(
+                op_field.0,
+                ParseRoundErrorKind::UnknownMove(op_field.1.to_string()),
+            )
+        })?;
+        let this_move = decoder
+            .decode(&op_move, second_field.1)
+            .map_err(|kind| ParseRoundError::new(second_field.0, kind))?;
+
+        Ok(RpsRound::new(op_move, this_move))
+    }
+
+    /// Parse every line into a round, panicking on the first line that can't be parsed.
+    ///
+    /// Use `try_from_lines` to handle bad input gracefully.
+    pub fn from_lines(round_lines: Lines, decoder: &impl ColumnDecoder) -> Vec<Self> {
+        match RpsRound::try_from_lines(round_lines, decoder, ErrorPolicy::FailFast) {
+            Ok(rounds) => rounds,
+            Err(errors) => panic!("Failed to parse strategy guide: {}", errors[0]),
+        }
+    }
+
+    /// Parse every line into a round.
+    ///
+    /// On failure, the returned errors carry the line number they were found on. With
+    /// `ErrorPolicy::FailFast` only the first error is returned.
+    pub fn try_from_lines(
+        round_lines: Lines,
+        decoder: &impl ColumnDecoder,
+        policy: ErrorPolicy,
+    ) -> Result<Vec<Self>, Vec<ParseRoundError>> {
+        let mut rps_rounds = Vec::new();
+        let mut errors = Vec::new();
+
+        for (idx, line) in round_lines.enumerate() {
+            match RpsRound::parse(line, decoder) {
+                Ok(round) => rps_rounds.push(round),
+                Err(err) => {
+                    errors.push(err.at_line(idx + 1));
+                    if policy == ErrorPolicy::FailFast {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(rps_rounds)
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn opponent_move(&self) -> Move {
+        self.opponent_move
+    }
+
+    pub fn recommended_move(&self) -> Move {
+        self.recommended_move
+    }
+
+    pub fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+
+    pub fn score(&self) -> u32 {
+        self.recommended_move.score() + self.outcome.score()
+    }
+
+    pub fn total_score(rounds: Vec<Self>) -> u32 {
+        let mut total = 0;
+
+        for round in rounds {
+            total += round.score();
+        }
+
+        total
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn create_move_from_char() {
+        let test_data = vec![
+            ("A", Some(Move::Rock), None),
+            ("X", None, Some(Move::Rock)),
+            ("B", Some(Move::Paper), None),
+            ("Y", None, Some(Move::Paper)),
+            ("C", Some(Move::Scissors), None),
+            ("Z", None, Some(Move::Scissors)),
+            ("Q", None, None),
+        ];
+
+        for data in test_data {
+            assert_eq!(data.1, Move::from_char(data.0));
+            assert_eq!(data.2, Move::from_code(data.0));
+        }
+    }
+
+    #[test]
+    fn create_move_from_outcome() {
+        let test_data = vec![
+            (Move::Rock, Outcome::Win, Move::Paper),
+            (Move::Paper, Outcome::Lose, Move::Rock),
+            (Move::Scissors, Outcome::Draw, Move::Scissors),
+        ];
+
+        for data in test_data {
+            assert_eq!(data.2, Move::from_outcome(&data.0, &data.1));
+        }
+    }
+
+    #[test]
+    fn create_outcome_from_moves() {
+        let test_data = vec![
+            (Move::Rock, Move::Rock, Outcome::Draw),
+            (Move::Paper, Move::Rock, Outcome::Win),
+            (Move::Scissors, Move::Rock, Outcome::Lose),
+            (Move::Scissors, Move::Scissors, Outcome::Draw),
+            (Move::Rock, Move::Scissors, Outcome::Win),
+            (Move::Paper, Move::Scissors, Outcome::Lose),
+        ];
+
+        for data in test_data {
+            assert_eq!(data.2, Outcome::from(&data.0, &data.1));
+        }
+    }
+
+    #[test]
+    fn create_outcome_from_code() {
+        let test_data = vec![
+            ("X", Some(Outcome::Lose)),
+            ("Y", Some(Outcome::Draw)),
+            ("Z", Some(Outcome::Win)),
+            ("A", None),
+        ];
+
+        for data in test_data {
+            assert_eq!(data.1, Outcome::from_code(data.0));
+        }
+    }
+
+    #[test]
+    fn create_round_from_string() {
+        let input = "A Y";
+
+        let expected = RpsRound {
+            opponent_move: Move::Rock,
+            recommended_move: Move::Paper,
+            outcome: Outcome::Win,
+        };
+
+        assert_eq!(Ok(expected), input.parse());
+    }
+
+    #[test]
+    fn create_rounds_with_second_column() {
+        let test_data = vec![
+            ("A Y", SecondColumn::Move, Move::Rock, Move::Paper),
+            ("B X", SecondColumn::Move, Move::Paper, Move::Rock),
+            ("C Z", SecondColumn::Move, Move::Scissors, Move::Scissors),
+            ("A Y", SecondColumn::Outcome, Move::Rock, Move::Rock),
+            ("B X", SecondColumn::Outcome, Move::Paper, Move::Rock),
+            ("C Z", SecondColumn::Outcome, Move::Scissors, Move::Rock),
+        ];
+
+        for data in test_data {
+            let round = RpsRound::parse(data.0, &data.1).unwrap();
+            assert_eq!(data.2, round.opponent_move);
+            assert_eq!(data.3, round.recommended_move);
+        }
+    }
+
+    #[test]
+    fn second_column_rejects_opponent_codes() {
+        for code in ["A", "B", "C"] {
+            assert_eq!(
+                Err(ParseRoundErrorKind::UnknownMove(code.to_string())),
+                SecondColumn::Move.decode(&Move::Rock, code)
+            );
+            assert_eq!(
+                Err(ParseRoundErrorKind::UnknownOutcome(code.to_string())),
+                SecondColumn::Outcome.decode(&Move::Rock, code)
+            );
+        }
+    }
+
+    #[test]
+    fn custom_column_decoder() {
+        /// Always mirrors the opponent, whatever the column says
+        struct Mirror;
+
+        impl ColumnDecoder for Mirror {
+            fn decode(&self, op_move: &Move, _code: &str) -> Result<Move, ParseRoundErrorKind> {
+                Ok(*op_move)
+            }
+        }
+
+        let rounds = RpsRound::from_lines("A Y\nB X\nC Z".lines(), &Mirror);
+
+        assert_eq!(15, RpsRound::total_score(rounds));
+    }
+
+    #[test]
+    fn round_parse_errors() {
+        let test_data = vec![
+            ("", 1, ParseRoundErrorKind::EmptyLine),
+            ("   ", 1, ParseRoundErrorKind::EmptyLine),
+            ("A", 1, ParseRoundErrorKind::FieldCount(1)),
+            ("A Y Z", 5, ParseRoundErrorKind::FieldCount(3)),
+            ("D Y", 1, ParseRoundErrorKind::UnknownMove("D".to_string())),
+            ("A  W", 4, ParseRoundErrorKind::UnknownMove("W".to_string())),
+            ("X Y", 1, ParseRoundErrorKind::UnknownMove("X".to_string())),
+            ("A B", 3, ParseRoundErrorKind::UnknownMove("B".to_string())),
+        ];
+
+        for data in test_data {
+            let err = RpsRound::try_from(data.0).unwrap_err();
+            assert_eq!(data.1, err.column);
+            assert_eq!(data.2, err.kind);
+        }
+
+        let err = RpsRound::parse("A B", &SecondColumn::Outcome).unwrap_err();
+        assert_eq!(3, err.column);
+        assert_eq!(
+            ParseRoundErrorKind::UnknownOutcome("B".to_string()),
+            err.kind
+        );
+    }
+
+    #[test]
+    fn display_round() {
+        let round = RpsRound::try_from("A Y").unwrap();
+
+        assert_eq!("Paper vs Rock: Win (2 + 6 = 8)", round.to_string());
+    }
+
+    #[test]
+    fn score_for_round() {
+        let input = "A Y";
+
+        let round = RpsRound::try_from(input).unwrap();
+
+        assert_eq!(8, round.score());
+    }
+
+    #[test]
+    fn total_round_scores() {
+        let input = "\
+A Y
+B X
+C Z";
+
+        let rounds = RpsRound::from_lines(input.lines(), &SecondColumn::Move);
+        assert_eq!(15, RpsRound::total_score(rounds));
+
+        let rounds = RpsRound::from_lines(input.lines(), &SecondColumn::Outcome);
+        assert_eq!(12, RpsRound::total_score(rounds));
+    }
+
+    #[test]
+    fn error_policy() {
+        let input = "\
+A Y
+B Q
+
+C Z";
+
+        let errors =
+            RpsRound::try_from_lines(input.lines(), &SecondColumn::Move, ErrorPolicy::FailFast)
+                .unwrap_err();
+        assert_eq!(1, errors.len());
+        assert_eq!(2, errors[0].line);
+
+        let errors =
+            RpsRound::try_from_lines(input.lines(), &SecondColumn::Move, ErrorPolicy::CollectAll)
+                .unwrap_err();
+        assert_eq!(
+            vec![(2, 3), (3, 1)],
+            errors
+                .iter()
+                .map(|err| (err.line, err.column))
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use clap::{Arg, ArgAction, Command};
+use rock_paper_scissors::{
+    explain::{TraceFormat, Tracer},
+    stream::{RoundReader, StreamError},
+    SecondColumn,
+};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    process::ExitCode,
+};
+
+/// Open the input file, or stdin when no path (or `-`) is given
+fn open_input(path: Option<&String>) -> io::Result<Box<dyn BufRead>> {
+    match path.map(String::as_str) {
+        None | Some("-") => Ok(Box::new(io::stdin().lock())),
+        Some(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+    }
+}
+
+fn main() -> ExitCode {
+    let cfg = Command::new("Rock Paper Scissors Scorer")
+        .author("Brenden Davidson")
+        .version("0.1.0")
+        .about("Advent of Code 2022: Day 2 solution")
+        .args([
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .action(ArgAction::Set)
+                .help("the input file to use, reads stdin when missing or '-'"),
+            Arg::new("part")
+                .short('p')
+                .long("part")
+                .action(ArgAction::Set)
+                .value_parser(["1", "2"])
+                .default_value("1")
+                .help("1 reads the second column as a move, 2 as an outcome"),
+            Arg::new("rounds")
+                .short('r')
+                .long("rounds")
+                .action(ArgAction::SetTrue)
+                .help("print a breakdown of every round"),
+            Arg::new("summary")
+                .short('s')
+                .long("summary")
+                .action(ArgAction::SetTrue)
+                .help("print move and outcome statistics"),
+            Arg::new("json")
+                .short('j')
+                .long("json")
+                .action(ArgAction::SetTrue)
+                .help("write JSON Lines instead of text"),
+        ])
+        .get_matches();
+
+    let input_path = cfg.get_one::<String>("input");
+    let decoder = match cfg.get_one::<String>("part").unwrap().as_str() {
+        "2" => SecondColumn::Outcome,
+        _ => SecondColumn::Move,
+    };
+    let format = if cfg.get_flag("json") {
+        TraceFormat::JsonLines
+    } else {
+        TraceFormat::Text
+    };
+    let show_summary = cfg.get_flag("summary");
+
+    let input = match open_input(input_path) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!(
+                "error: failed to open '{}': {err}",
+                input_path.map_or("-", String::as_str)
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut tracer = Tracer::new(io::stdout().lock(), format)
+        .show_rounds(cfg.get_flag("rounds"))
+        .show_summary(show_summary);
+    let mut bad_lines = 0;
+
+    for round in RoundReader::new(input, decoder) {
+        let written = match round {
+            Ok(round) => tracer.round(&round),
+            Err(err @ (StreamError::Parse(_) | StreamError::InvalidUtf8 { .. })) => {
+                eprintln!("error: {err}");
+                bad_lines += 1;
+                Ok(())
+            }
+            Err(err) => {
+                eprintln!("error: {err}");
+                return ExitCode::FAILURE;
+            }
+        };
+
+        if let Err(err) = written {
+            eprintln!("error: failed to write output: {err}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if bad_lines > 0 {
+        eprintln!("error: {bad_lines} line(s) could not be parsed");
+        return ExitCode::FAILURE;
+    }
+
+    let summary = match tracer.finish() {
+        Ok(summary) => summary,
+        Err(err) => {
+            eprintln!("error: failed to write output: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if !show_summary {
+        match format {
+            TraceFormat::Text => println!("Total score: {}", summary.total_score),
+            TraceFormat::JsonLines => println!(
+                "{{\"rounds\":{},\"total_score\":{}}}",
+                summary.rounds, summary.total_score
+            ),
+        }
+    }
+
+    ExitCode::SUCCESS
+}
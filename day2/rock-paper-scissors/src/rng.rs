@@ -0,0 +1,45 @@
+/// Small seedable SplitMix64 generator, good enough for simulations and test data
+#[derive(Debug, Clone)]
+pub(crate) struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `0..bound`
+    pub(crate) fn below(&mut self, bound: u64) -> u64 {
+        // Multiply-shift keeps the bias negligible for the small bounds used here
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+
+    /// True with probability `rate`
+    pub(crate) fn chance(&mut self, rate: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < rate
+    }
+
+    /// An index into `weights`, picked in proportion to each weight
+    pub(crate) fn weighted(&mut self, weights: &[u32]) -> usize {
+        let total: u64 = weights.iter().map(|&w| u64::from(w)).sum();
+        let mut pick = self.below(total);
+
+        for (idx, &weight) in weights.iter().enumerate() {
+            if pick < u64::from(weight) {
+                return idx;
+            }
+            pick -= u64::from(weight);
+        }
+
+        weights.len() - 1
+    }
+}
@@ -0,0 +1,296 @@
+use crate::{rng::SplitMix64, Move, Outcome, RpsRound};
+
+/// The move with the highest count. Ties favour the earlier move so models stay deterministic.
+fn most_frequent(counts: &[u32; 3]) -> Move {
+    let mut best = 0;
+    for id in 1..3 {
+        if counts[id] > counts[best] {
+            best = id;
+        }
+    }
+
+    Move::ALL[best]
+}
+
+/// The move that beats `predicted`
+fn counter(predicted: &Move) -> Move {
+    Move::from_outcome(predicted, &Outcome::Win)
+}
+
+/// A model of how the opponent picks moves during a match
+pub trait Opponent {
+    /// Called before every match
+    fn reset(&mut self) {}
+
+    /// Pick the move for the next round
+    fn next_move(&mut self) -> Move;
+
+    /// Learn what the guide played in the round that just finished
+    fn observe(&mut self, _our_move: &Move) {}
+}
+
+/// Picks every move uniformly at random
+#[derive(Debug, Clone)]
+pub struct UniformRandom {
+    rng: SplitMix64,
+}
+
+impl UniformRandom {
+    pub fn new(seed: u64) -> Self {
+        UniformRandom {
+            rng: SplitMix64::new(seed),
+        }
+    }
+}
+
+impl Opponent for UniformRandom {
+    fn next_move(&mut self) -> Move {
+        Move::ALL[self.rng.below(3) as usize]
+    }
+}
+
+/// Counters whatever move the guide has played most often so far in the match
+#[derive(Debug, Clone, Default)]
+pub struct FrequencyCounter {
+    counts: [u32; 3],
+}
+
+impl Opponent for FrequencyCounter {
+    fn reset(&mut self) {
+        self.counts = [0; 3];
+    }
+
+    fn next_move(&mut self) -> Move {
+        counter(&most_frequent(&self.counts))
+    }
+
+    fn observe(&mut self, our_move: &Move) {
+        self.counts[our_move.id()] += 1;
+    }
+}
+
+/// Assumes the guide repeats its previous move and counters it
+#[derive(Debug, Clone, Default)]
+pub struct LastMoveRepeat {
+    last: Option<Move>,
+}
+
+impl Opponent for LastMoveRepeat {
+    fn reset(&mut self) {
+        self.last = None;
+    }
+
+    fn next_move(&mut self) -> Move {
+        match &self.last {
+            Some(last) => counter(last),
+            None => Move::Rock,
+        }
+    }
+
+    fn observe(&mut self, our_move: &Move) {
+        self.last = Some(*our_move);
+    }
+}
+
+/// Predicts the guide's next move from its previous one using transition counts
+#[derive(Debug, Clone)]
+pub struct MarkovPredictor {
+    /// `transitions[a][b]` counts how often move `b` followed move `a`
+    transitions: [[u32; 3]; 3],
+    /// How often each move opens the guide, or follows nothing
+    openings: [u32; 3],
+    last: Option<Move>,
+}
+
+impl MarkovPredictor {
+    /// Build the transition table from the moves recommended by `guide`
+    pub fn train(guide: &[RpsRound]) -> Self {
+        let mut transitions = [[0; 3]; 3];
+        let mut openings = [0; 3];
+
+        if let Some(first) = guide.first() {
+            openings[first.recommended_move.id()] += 1;
+        }
+        for pair in guide.windows(2) {
+            transitions[pair[0].recommended_move.id()][pair[1].recommended_move.id()] += 1;
+        }
+
+        MarkovPredictor {
+            transitions,
+            openings,
+            last: None,
+        }
+    }
+}
+
+impl Opponent for MarkovPredictor {
+    fn reset(&mut self) {
+        self.last = None;
+    }
+
+    fn next_move(&mut self) -> Move {
+        let predicted = match &self.last {
+            Some(last) => most_frequent(&self.transitions[last.id()]),
+            None => most_frequent(&self.openings),
+        };
+
+        counter(&predicted)
+    }
+
+    fn observe(&mut self, our_move: &Move) {
+        self.last = Some(*our_move);
+    }
+}
+
+/// Aggregated results of playing a guide many times
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationReport {
+    pub matches: usize,
+    pub mean_score: f64,
+    /// Population variance of the per-match total score
+    pub score_variance: f64,
+    pub wins: u64,
+    pub draws: u64,
+    pub losses: u64,
+    /// How many times each guide line lost, indexed by line
+    pub line_losses: Vec<u64>,
+}
+
+impl SimulationReport {
+    fn rounds(&self) -> u64 {
+        self.wins + self.draws + self.losses
+    }
+
+    fn rate(&self, count: u64) -> f64 {
+        match self.rounds() {
+            0 => 0.0,
+            rounds => count as f64 / rounds as f64,
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        self.rate(self.wins)
+    }
+
+    pub fn draw_rate(&self) -> f64 {
+        self.rate(self.draws)
+    }
+
+    pub fn loss_rate(&self) -> f64 {
+        self.rate(self.losses)
+    }
+
+    /// 1-based numbers of the guide lines that lost in at least `threshold` of the matches
+    pub fn exploitable_lines(&self, threshold: f64) -> Vec<usize> {
+        if self.matches == 0 {
+            return Vec::new();
+        }
+
+        self.line_losses
+            .iter()
+            .enumerate()
+            .filter(|(_, &losses)| losses as f64 / self.matches as f64 >= threshold)
+            .map(|(idx, _)| idx + 1)
+            .collect()
+    }
+}
+
+/// Play the moves recommended by `guide` against `opponent`, `matches` times over
+pub fn simulate(
+    guide: &[RpsRound],
+    opponent: &mut impl Opponent,
+    matches: usize,
+) -> SimulationReport {
+    let mut totals = Vec::with_capacity(matches);
+    let mut report = SimulationReport {
+        matches,
+        mean_score: 0.0,
+        score_variance: 0.0,
+        wins: 0,
+        draws: 0,
+        losses: 0,
+        line_losses: vec![0; guide.len()],
+    };
+
+    for _ in 0..matches {
+        opponent.reset();
+        let mut total = 0;
+
+        for (idx, round) in guide.iter().enumerate() {
+            let played = RpsRound::new(opponent.next_move(), round.recommended_move);
+            opponent.observe(&played.recommended_move);
+
+            match played.outcome {
+                Outcome::Win => report.wins += 1,
+                Outcome::Draw => report.draws += 1,
+                Outcome::Lose => {
+                    report.losses += 1;
+                    report.line_losses[idx] += 1;
+                }
+            }
+            total += u64::from(played.score());
+        }
+
+        totals.push(total as f64);
+    }
+
+    if matches > 0 {
+        report.mean_score = totals.iter().sum::<f64>() / matches as f64;
+        report.score_variance = totals
+            .iter()
+            .map(|total| (total - report.mean_score).powi(2))
+            .sum::<f64>()
+            / matches as f64;
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SecondColumn;
+
+    fn guide(input: &str) -> Vec<RpsRound> {
+        RpsRound::from_lines(input.lines(), &SecondColumn::Move)
+    }
+
+    #[test]
+    fn seeded_random_is_repeatable() {
+        let guide = guide("A Y\nB X\nC Z\nA X");
+
+        let first = simulate(&guide, &mut UniformRandom::new(42), 200);
+        let second = simulate(&guide, &mut UniformRandom::new(42), 200);
+        assert_eq!(first, second);
+
+        assert_eq!(800, first.wins + first.draws + first.losses);
+        assert!((first.win_rate() + first.draw_rate() + first.loss_rate() - 1.0).abs() < 1e-9);
+        assert!(first.score_variance > 0.0);
+    }
+
+    #[test]
+    fn repeated_moves_are_exploited() {
+        // The guide always plays Rock, so a repeat predictor wins every round after the first
+        let guide = guide("A X\nA X\nA X\nA X");
+
+        let report = simulate(&guide, &mut LastMoveRepeat::default(), 10);
+        assert_eq!(30, report.losses);
+        assert_eq!(10, report.draws);
+        assert_eq!(vec![2, 3, 4], report.exploitable_lines(1.0));
+        assert_eq!(0.0, report.score_variance);
+
+        // With nothing observed yet, the frequency model counters Rock straight away
+        let report = simulate(&guide, &mut FrequencyCounter::default(), 10);
+        assert_eq!(vec![1, 2, 3, 4], report.exploitable_lines(0.5));
+    }
+
+    #[test]
+    fn markov_predictor_learns_cycles() {
+        // Rock -> Paper -> Scissors -> Rock...
+        let guide = guide("A X\nA Y\nA Z\nA X\nA Y\nA Z");
+
+        let report = simulate(&guide, &mut MarkovPredictor::train(&guide), 1);
+        assert_eq!(6, report.losses);
+        assert_eq!(12.0, report.mean_score);
+    }
+}
@@ -0,0 +1,272 @@
+use crate::{Move, Outcome, RpsRound};
+
+/// Whether the solver should look for the best or the worst total
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Objective {
+    Maximise,
+    Minimise,
+}
+
+/// Limits on which move sequences are allowed
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Constraints {
+    /// Never win more than this many rounds
+    pub max_wins: Option<usize>,
+    /// Never play the same move more than this many times in a row
+    pub max_repeats: Option<usize>,
+}
+
+/// The moves picked by the solver and the total they score
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Solution {
+    pub score: u32,
+    pub moves: Vec<Move>,
+}
+
+/// The opponent column of a parsed strategy guide
+pub fn opponent_moves(guide: &[RpsRound]) -> Vec<Move> {
+    guide.iter().map(|round| round.opponent_move).collect()
+}
+
+/// The best possible total against `opponent_moves`
+pub fn max_score(opponent_moves: &[Move]) -> Solution {
+    solve(opponent_moves, Objective::Maximise, &Constraints::default()).unwrap()
+}
+
+/// The worst possible total against `opponent_moves`
+pub fn min_score(opponent_moves: &[Move]) -> Solution {
+    solve(opponent_moves, Objective::Minimise, &Constraints::default()).unwrap()
+}
+
+/// Pick a move for every round so the total is as high (or low) as `constraints` allow.
+///
+/// Returns `None` when no sequence of moves satisfies the constraints. When several sequences
+/// share the best total, the same one is always returned.
+///
+/// Each round keeps a 4 byte parent for every (wins, move, run) state, so memory grows with
+/// rounds × (`max_wins` + 1) × 3 × `max_repeats`. That's about 75 MB for 2,500 rounds with
+/// `max_wins` near the round count, and three times that with `max_repeats` of 3. Panics if
+/// a round has more than `u32::MAX` states.
+pub fn solve(
+    opponent_moves: &[Move],
+    objective: Objective,
+    constraints: &Constraints,
+) -> Option<Solution> {
+    if opponent_moves.is_empty() {
+        return Some(Solution {
+            score: 0,
+            moves: Vec::new(),
+        });
+    }
+    if constraints.max_repeats == Some(0) {
+        return None;
+    }
+
+    // A state is (wins so far, last move, length of the current run). Untracked constraints
+    // collapse their dimension to a single slot.
+    let win_slots = constraints
+        .max_wins
+        .map_or(1, |k| k.min(opponent_moves.len()) + 1);
+    // No run can be longer than the guide
+    let run_slots = constraints
+        .max_repeats
+        .map_or(1, |r| r.min(opponent_moves.len()));
+    let state_count = win_slots * Move::ALL.len() * run_slots;
+    assert!(
+        u32::try_from(state_count).is_ok(),
+        "{state_count} states per round don't fit in a u32 parent index"
+    );
+    let index =
+        |wins: usize, last: usize, run: usize| (wins * Move::ALL.len() + last) * run_slots + run;
+
+    let is_better = |candidate: u32, current: Option<u32>| match (objective, current) {
+        (_, None) => true,
+        (Objective::Maximise, Some(current)) => candidate > current,
+        (Objective::Minimise, Some(current)) => candidate < current,
+    };
+
+    let mut scores: Vec<Option<u32>> = vec![None; state_count];
+    // `parents[i][state]` is the state the best path came from before round `i`
+    let mut parents: Vec<Vec<u32>> = Vec::with_capacity(opponent_moves.len());
+
+    for (id, this_move) in Move::ALL.iter().enumerate() {
+        let round = RpsRound::new(opponent_moves[0], *this_move);
+        let wins = match constraints.max_wins {
+            Some(_) => usize::from(round.outcome == Outcome::Win),
+            None => 0,
+        };
+        if wins < win_slots {
+            scores[index(wins, id, 0)] = Some(round.score());
+        }
+    }
+    parents.push(vec![u32::MAX; state_count]);
+
+    for op_move in &opponent_moves[1..] {
+        let mut next_scores: Vec<Option<u32>> = vec![None; state_count];
+        let mut next_parents = vec![u32::MAX; state_count];
+
+        for wins in 0..win_slots {
+            for last in 0..Move::ALL.len() {
+                for run in 0..run_slots {
+                    let state = index(wins, last, run);
+                    let Some(score) = scores[state] else {
+                        continue;
+                    };
+
+                    for (id, this_move) in Move::ALL.iter().enumerate() {
+                        let round = RpsRound::new(*op_move, *this_move);
+
+                        let next_wins = match constraints.max_wins {
+                            Some(_) => wins + usize::from(round.outcome == Outcome::Win),
+                            None => 0,
+                        };
+                        let next_run = match constraints.max_repeats {
+                            Some(_) if id == last => run + 1,
+                            _ => 0,
+                        };
+                        if next_wins >= win_slots || next_run >= run_slots {
+                            continue;
+                        }
+
+                        let next_state = index(next_wins, id, next_run);
+                        let next_score = score + round.score();
+                        if is_better(next_score, next_scores[next_state]) {
+                            next_scores[next_state] = Some(next_score);
+                            next_parents[next_state] = state as u32;
+                        }
+                    }
+                }
+            }
+        }
+
+        scores = next_scores;
+        parents.push(next_parents);
+    }
+
+    let mut best: Option<(usize, u32)> = None;
+    for (state, score) in scores.iter().enumerate() {
+        if let Some(score) = *score {
+            if is_better(score, best.map(|(_, best_score)| best_score)) {
+                best = Some((state, score));
+            }
+        }
+    }
+    let (mut state, score) = best?;
+
+    let mut moves = Vec::with_capacity(opponent_moves.len());
+    for round_parents in parents.iter().rev() {
+        moves.push(Move::ALL[(state / run_slots) % Move::ALL.len()]);
+        state = round_parents[state] as usize;
+    }
+    moves.reverse();
+
+    Some(Solution { score, moves })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SecondColumn;
+
+    fn total(opponent_moves: &[Move], moves: &[Move]) -> u32 {
+        opponent_moves
+            .iter()
+            .zip(moves)
+            .map(|(op_move, this_move)| RpsRound::new(*op_move, *this_move).score())
+            .sum()
+    }
+
+    #[test]
+    fn unconstrained_extremes() {
+        let guide = RpsRound::from_lines("A Y\nB X\nC Z".lines(), &SecondColumn::Move);
+        let op_moves = opponent_moves(&guide);
+
+        let best = max_score(&op_moves);
+        // Paper beats Rock (8), Scissors beats Paper (9), Rock beats Scissors (7)
+        assert_eq!(24, best.score);
+        assert_eq!(vec![Move::Paper, Move::Scissors, Move::Rock], best.moves);
+
+        let worst = min_score(&op_moves);
+        assert_eq!(6, worst.score);
+        assert_eq!(worst.score, total(&op_moves, &worst.moves));
+    }
+
+    #[test]
+    fn limited_wins() {
+        let op_moves = vec![Move::Rock; 4];
+
+        for max_wins in 0..=4 {
+            let constraints = Constraints {
+                max_wins: Some(max_wins),
+                max_repeats: None,
+            };
+            let solution = solve(&op_moves, Objective::Maximise, &constraints).unwrap();
+
+            let wins = solution
+                .moves
+                .iter()
+                .filter(|this_move| **this_move == Move::Paper)
+                .count();
+            assert_eq!(max_wins, wins);
+            // Every round that can't win draws with Rock (4) rather than losing with Scissors (3)
+            assert_eq!((8 * max_wins + 4 * (4 - max_wins)) as u32, solution.score);
+            assert_eq!(solution.score, total(&op_moves, &solution.moves));
+        }
+    }
+
+    #[test]
+    fn limited_repeats() {
+        let op_moves = vec![Move::Rock; 7];
+        let constraints = Constraints {
+            max_wins: None,
+            max_repeats: Some(2),
+        };
+
+        let solution = solve(&op_moves, Objective::Maximise, &constraints).unwrap();
+        for window in solution.moves.windows(3) {
+            assert!(!(window[0] == window[1] && window[1] == window[2]));
+        }
+        // Paper twice, then the next best move once, repeated: 8 8 4 8 8 4 8
+        assert_eq!(48, solution.score);
+        assert_eq!(solution.score, total(&op_moves, &solution.moves));
+
+        let constraints = Constraints {
+            max_wins: None,
+            max_repeats: Some(0),
+        };
+        assert_eq!(None, solve(&op_moves, Objective::Maximise, &constraints));
+    }
+
+    #[test]
+    fn combined_constraints() {
+        let op_moves = vec![Move::Rock, Move::Rock, Move::Paper, Move::Scissors];
+        let constraints = Constraints {
+            max_wins: Some(1),
+            max_repeats: Some(1),
+        };
+
+        let solution = solve(&op_moves, Objective::Maximise, &constraints).unwrap();
+        assert_eq!(solution.score, total(&op_moves, &solution.moves));
+        assert!(solution.moves.windows(2).all(|pair| pair[0] != pair[1]));
+        let wins = op_moves
+            .iter()
+            .zip(&solution.moves)
+            .filter(|(op_move, this_move)| Outcome::from(this_move, op_move) == Outcome::Win)
+            .count();
+        assert!(wins <= 1);
+    }
+
+    #[test]
+    fn limits_beyond_the_guide() {
+        let op_moves = vec![Move::Rock, Move::Paper, Move::Rock];
+        let constraints = Constraints {
+            max_wins: Some(usize::MAX),
+            max_repeats: Some(usize::MAX),
+        };
+
+        assert_eq!(
+            Some(max_score(&op_moves)),
+            solve(&op_moves, Objective::Maximise, &constraints)
+        );
+    }
+}
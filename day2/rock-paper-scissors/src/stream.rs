@@ -0,0 +1,279 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    io::{self, BufRead, Read},
+    str::{self, Utf8Error},
+};
+
+use crate::{ColumnDecoder, ParseRoundError, RpsRound};
+
+/// Error produced while reading rounds from a stream
+#[derive(Debug)]
+pub enum StreamError {
+    /// The underlying reader failed
+    Io(io::Error),
+    /// A line could not be parsed
+    Parse(ParseRoundError),
+    /// A line ran past the reader's length limit before ending
+    LineTooLong { line: usize, limit: usize },
+    /// A line was not valid UTF-8
+    InvalidUtf8 { line: usize, err: Utf8Error },
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            StreamError::Io(err) => write!(f, "failed to read strategy guide: {err}"),
+            StreamError::Parse(err) => write!(f, "{err}"),
+            StreamError::LineTooLong { line, limit } => {
+                write!(f, "line {line}: longer than {limit} bytes")
+            }
+            StreamError::InvalidUtf8 { line, err } => write!(f, "line {line}: {err}"),
+        }
+    }
+}
+
+impl Error for StreamError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StreamError::Io(err) => Some(err),
+            StreamError::Parse(err) => Some(err),
+            StreamError::LineTooLong { .. } => None,
+            StreamError::InvalidUtf8 { err, .. } => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for StreamError {
+    fn from(err: io::Error) -> Self {
+        StreamError::Io(err)
+    }
+}
+
+impl From<ParseRoundError> for StreamError {
+    fn from(err: ParseRoundError) -> Self {
+        StreamError::Parse(err)
+    }
+}
+
+/// Default limit on the length of a single line, in bytes
+pub const MAX_LINE_LEN: usize = 4096;
+
+/// Reads rounds one line at a time from any `BufRead`, keeping a running score.
+///
+/// Only the current line is held in memory, and lines longer than the limit are rejected
+/// rather than buffered, so arbitrarily large guides can be scored. The totals only include
+/// rounds that parsed successfully. Reading stops after an overlong line.
+pub struct RoundReader<R, D> {
+    reader: R,
+    decoder: D,
+    buffer: Vec<u8>,
+    max_line_len: usize,
+    line: usize,
+    rounds: u64,
+    total_score: u64,
+    done: bool,
+}
+
+impl<R: BufRead, D: ColumnDecoder> RoundReader<R, D> {
+    pub fn new(reader: R, decoder: D) -> Self {
+        RoundReader {
+            reader,
+            decoder,
+            buffer: Vec::new(),
+            max_line_len: MAX_LINE_LEN,
+            line: 0,
+            rounds: 0,
+            total_score: 0,
+            done: false,
+        }
+    }
+
+    /// Reject lines longer than `max_line_len` bytes, not counting the line ending
+    pub fn max_line_len(mut self, max_line_len: usize) -> Self {
+        self.max_line_len = max_line_len;
+        self
+    }
+
+    /// Number of lines read so far, including bad ones
+    pub fn lines_read(&self) -> usize {
+        self.line
+    }
+
+    /// Number of rounds successfully parsed so far
+    pub fn rounds(&self) -> u64 {
+        self.rounds
+    }
+
+    /// Sum of the scores of every round parsed so far
+    pub fn total_score(&self) -> u64 {
+        self.total_score
+    }
+
+    /// Consume the rest of the stream, stopping at the first error
+    pub fn finish(mut self) -> Result<u64, StreamError> {
+        for round in self.by_ref() {
+            round?;
+        }
+
+        Ok(self.total_score)
+    }
+}
+
+impl<R: BufRead, D: ColumnDecoder> Iterator for RoundReader<R, D> {
+    type Item = Result<RpsRound, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        // Room for the longest allowed line, its "\r\n", and one byte to spot overlong ones
+        let cap = self.max_line_len as u64 + 3;
+        self.buffer.clear();
+        match (&mut self.reader)
+            .take(cap)
+            .read_until(b'\n', &mut self.buffer)
+        {
+            Ok(0) => return None,
+            Ok(_) => self.line += 1,
+            Err(err) => return Some(Err(err.into())),
+        }
+
+        // Check the length before decoding, since the cap may have split a character
+        let mut line = self.buffer.as_slice();
+        while let [rest @ .., b'\n' | b'\r'] = line {
+            line = rest;
+        }
+        if line.len() > self.max_line_len {
+            self.done = true;
+            return Some(Err(StreamError::LineTooLong {
+                line: self.line,
+                limit: self.max_line_len,
+            }));
+        }
+
+        let line = match str::from_utf8(line) {
+            Ok(line) => line,
+            Err(err) => {
+                return Some(Err(StreamError::InvalidUtf8 {
+                    line: self.line,
+                    err,
+                }))
+            }
+        };
+
+        let round = match RpsRound::parse(line, &self.decoder) {
+            Ok(round) => round,
+            Err(err) => return Some(Err(err.at_line(self.line).into())),
+        };
+
+        self.rounds += 1;
+        self.total_score += u64::from(round.score());
+
+        Some(Ok(round))
+    }
+}
+
+/// Score every round in `reader` without holding the guide in memory
+pub fn score_reader(reader: impl BufRead, decoder: impl ColumnDecoder) -> Result<u64, StreamError> {
+    RoundReader::new(reader, decoder).finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ParseRoundErrorKind, SecondColumn};
+
+    #[test]
+    fn running_totals() {
+        let input = "A Y\r\nB X\r\nC Z\r\n";
+        let mut reader = RoundReader::new(input.as_bytes(), SecondColumn::Move);
+
+        let mut totals = Vec::new();
+        while let Some(round) = reader.next() {
+            round.unwrap();
+            totals.push(reader.total_score());
+        }
+
+        assert_eq!(vec![8, 9, 15], totals);
+        assert_eq!(3, reader.rounds());
+    }
+
+    #[test]
+    fn matches_in_memory_parsing() {
+        let input = "A Y\nB X\nC Z";
+
+        for decoder in [SecondColumn::Move, SecondColumn::Outcome] {
+            let rounds = RpsRound::from_lines(input.lines(), &decoder);
+            assert_eq!(
+                u64::from(RpsRound::total_score(rounds)),
+                score_reader(input.as_bytes(), decoder).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_overlong_lines() {
+        let input = format!("A Y\n{}\nB X\n", "A".repeat(100));
+        let mut reader = RoundReader::new(input.as_bytes(), SecondColumn::Move).max_line_len(10);
+
+        assert!(reader.next().unwrap().is_ok());
+        assert!(matches!(
+            reader.next(),
+            Some(Err(StreamError::LineTooLong { line: 2, limit: 10 }))
+        ));
+        assert!(reader.next().is_none());
+
+        // Line endings don't count towards the limit
+        let input = "A Y\r\nB X";
+        let reader = RoundReader::new(input.as_bytes(), SecondColumn::Move).max_line_len(3);
+        assert_eq!(9, reader.finish().unwrap());
+    }
+
+    #[test]
+    fn overlong_line_split_inside_a_character() {
+        let input = format!("{}\nA Y\n", "é".repeat(10));
+        let mut reader = RoundReader::new(input.as_bytes(), SecondColumn::Move).max_line_len(6);
+
+        assert!(matches!(
+            reader.next(),
+            Some(Err(StreamError::LineTooLong { line: 1, limit: 6 }))
+        ));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn invalid_utf8_keeps_line_numbers() {
+        let input = b"A Y\n\xff\xfe\nB X\nC Q\n";
+        let mut reader = RoundReader::new(&input[..], SecondColumn::Move);
+
+        assert!(reader.next().unwrap().is_ok());
+        assert!(matches!(
+            reader.next(),
+            Some(Err(StreamError::InvalidUtf8 { line: 2, .. }))
+        ));
+        assert!(reader.next().unwrap().is_ok());
+        match reader.next() {
+            Some(Err(StreamError::Parse(err))) => assert_eq!(4, err.line),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+        assert_eq!(4, reader.lines_read());
+    }
+
+    #[test]
+    fn reports_line_of_bad_round() {
+        let input = "A Y\nB X\nC W\n";
+
+        match score_reader(input.as_bytes(), SecondColumn::Outcome) {
+            Err(StreamError::Parse(err)) => {
+                assert_eq!(3, err.line);
+                assert_eq!(
+                    ParseRoundErrorKind::UnknownOutcome("W".to_string()),
+                    err.kind
+                );
+            }
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rucksack::{
+    batch::{group_total, par_group_total, par_rucksack_total, rucksack_total},
+    PartialGroup, Rucksack,
+};
+
+/// Builds `count` rucksack lines of 16 to 48 items from a fixed seed
+fn inventory(count: usize) -> Vec<String> {
+    let mut rng = fastrand::Rng::with_seed(0x2022_0003);
+
+    (0..count)
+        .map(|_| {
+            let len = 2 * (8 + rng.usize(..17));
+            (0..len).map(|_| rng.alphabetic()).collect()
+        })
+        .collect()
+}
+
+fn priorities(c: &mut Criterion) {
+    let mut group = c.benchmark_group("priorities");
+
+    for count in [10_000, 1_000_000] {
+        let lines = inventory(count);
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let rucksacks = Rucksack::from_lines(&lines);
+
+        group.bench_with_input(
+            BenchmarkId::new("rucksacks/sequential", count),
+            &rucksacks,
+            |b, rucksacks| b.iter(|| rucksack_total(black_box(rucksacks))),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("rucksacks/parallel", count),
+            &rucksacks,
+            |b, rucksacks| b.iter(|| par_rucksack_total(black_box(rucksacks))),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("groups/sequential", count),
+            &rucksacks,
+            |b, rucksacks| b.iter(|| group_total(black_box(rucksacks), 3, PartialGroup::Keep)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("groups/parallel", count),
+            &rucksacks,
+            |b, rucksacks| b.iter(|| par_group_total(black_box(rucksacks), 3, PartialGroup::Keep)),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, priorities);
+criterion_main!(benches);
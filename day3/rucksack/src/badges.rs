@@ -0,0 +1,253 @@
+use crate::{priority::PriorityScheme, ItemSet, Rucksack};
+
+/// A group of rucksacks that share exactly one item type
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BadgeGroup {
+    /// Indices of the group's rucksacks in the input, in ascending order
+    pub members: Vec<usize>,
+    pub badge: char,
+}
+
+/// Partitions unordered rucksacks into groups of `group_size` where every group shares exactly
+/// one item type (its badge).
+///
+/// The search is exhaustive, so `None` means no such partition exists. That includes the case
+/// where the rucksack count isn't a multiple of `group_size`.
+pub fn find_badge_groups<S: PriorityScheme>(
+    rucksacks: &[Rucksack<S>],
+    group_size: usize,
+) -> Option<Vec<BadgeGroup>> {
+    if group_size == 0 || rucksacks.len() % group_size != 0 {
+        return None;
+    }
+
+    let items: Vec<ItemSet> = rucksacks.iter().map(Rucksack::items).collect();
+    let mut search = Search::new(&items, group_size);
+
+    let mut chosen = Vec::new();
+    if !search.cover(&mut chosen) {
+        return None;
+    }
+
+    let mut groups: Vec<BadgeGroup> = chosen
+        .into_iter()
+        .map(|candidate| BadgeGroup {
+            members: search.members(candidate).to_vec(),
+            badge: rucksacks[0]
+                .scheme()
+                .item(search.badges[candidate])
+                .expect("badges come from the scheme"),
+        })
+        .collect();
+    groups.sort_by_key(|group| group.members[0]);
+
+    Some(groups)
+}
+
+/// Exact cover search over every candidate group that has a unique badge.
+///
+/// Each step picks the ungrouped rucksack with the fewest remaining candidates, so dead ends
+/// are found as soon as some rucksack has nowhere left to go.
+struct Search {
+    group_size: usize,
+    /// Members of every candidate group, `group_size` entries per candidate
+    members: Vec<usize>,
+    /// Priority of every candidate group's badge
+    badges: Vec<u8>,
+    /// Candidates each rucksack belongs to
+    containing: Vec<Vec<usize>>,
+    alive: Vec<bool>,
+    /// Number of alive candidates each rucksack belongs to
+    options: Vec<usize>,
+    grouped: Vec<bool>,
+}
+
+impl Search {
+    fn new(items: &[ItemSet], group_size: usize) -> Self {
+        let mut search = Search {
+            group_size,
+            members: Vec::new(),
+            badges: Vec::new(),
+            containing: vec![Vec::new(); items.len()],
+            alive: Vec::new(),
+            options: vec![0; items.len()],
+            grouped: vec![false; items.len()],
+        };
+
+        let mut current = Vec::with_capacity(group_size);
+        for first in 0..items.len() {
+            current.push(first);
+            search.collect_candidates(items, &mut current, items[first]);
+            current.pop();
+        }
+
+        search
+    }
+
+    /// Records every group extending `current` whose members share exactly one item
+    fn collect_candidates(&mut self, items: &[ItemSet], current: &mut Vec<usize>, shared: ItemSet) {
+        if current.len() == self.group_size {
+            if shared.len() == 1 {
+                let candidate = self.badges.len();
+                for &member in current.iter() {
+                    self.containing[member].push(candidate);
+                    self.options[member] += 1;
+                }
+                self.members.extend_from_slice(current);
+                self.badges.push(shared.first_priority().unwrap());
+                self.alive.push(true);
+            }
+            return;
+        }
+
+        for next in (current[current.len() - 1] + 1)..items.len() {
+            let next_shared = shared & items[next];
+            if next_shared.is_empty() {
+                continue;
+            }
+
+            current.push(next);
+            self.collect_candidates(items, current, next_shared);
+            current.pop();
+        }
+    }
+
+    fn members(&self, candidate: usize) -> &[usize] {
+        &self.members[candidate * self.group_size..(candidate + 1) * self.group_size]
+    }
+
+    /// Picks candidates until every rucksack is grouped, returning false if that's impossible
+    fn cover(&mut self, chosen: &mut Vec<usize>) -> bool {
+        let next = (0..self.grouped.len())
+            .filter(|&rucksack| !self.grouped[rucksack])
+            .min_by_key(|&rucksack| self.options[rucksack]);
+        let rucksack = match next {
+            Some(rucksack) => rucksack,
+            None => return true,
+        };
+
+        let candidates: Vec<usize> = self.containing[rucksack]
+            .iter()
+            .copied()
+            .filter(|&candidate| self.alive[candidate])
+            .collect();
+
+        for candidate in candidates {
+            let removed = self.choose(candidate);
+            chosen.push(candidate);
+
+            if self.cover(chosen) {
+                return true;
+            }
+
+            chosen.pop();
+            self.unchoose(candidate, removed);
+        }
+
+        false
+    }
+
+    /// Groups the members of `candidate`, killing every candidate that overlaps it
+    fn choose(&mut self, candidate: usize) -> Vec<usize> {
+        let mut removed = Vec::new();
+
+        for idx in 0..self.group_size {
+            let member = self.members(candidate)[idx];
+            self.grouped[member] = true;
+
+            for pos in 0..self.containing[member].len() {
+                let other = self.containing[member][pos];
+                if !self.alive[other] {
+                    continue;
+                }
+
+                self.alive[other] = false;
+                for other_idx in 0..self.group_size {
+                    let other_member = self.members(other)[other_idx];
+                    self.options[other_member] -= 1;
+                }
+                removed.push(other);
+            }
+        }
+
+        removed
+    }
+
+    fn unchoose(&mut self, candidate: usize, removed: Vec<usize>) {
+        for other in removed {
+            self.alive[other] = true;
+            for other_idx in 0..self.group_size {
+                let other_member = self.members(other)[other_idx];
+                self.options[other_member] += 1;
+            }
+        }
+
+        for idx in 0..self.group_size {
+            let member = self.members(candidate)[idx];
+            self.grouped[member] = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::priority::DefaultScheme;
+
+    #[test]
+    fn shuffled_groups() {
+        let lines = [
+            "ttgJtRGJQctTZtZT",
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "CrZsJsPPZsGzwwsLwLmpwMDw",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "PmmdzqPrVvPwwTWBwg",
+        ];
+        let rucksacks = Rucksack::from_lines(&lines);
+
+        let groups = find_badge_groups(&rucksacks, 3).unwrap();
+        assert_eq!(2, groups.len());
+
+        let mut seen: Vec<usize> = Vec::new();
+        for group in &groups {
+            let members: Vec<Rucksack> = group
+                .members
+                .iter()
+                .map(|&idx| Rucksack::from_string(lines[idx]))
+                .collect();
+            let shared = Rucksack::group_shared_items(&members);
+
+            assert_eq!(1, shared.len());
+            assert!(shared.contains(group.badge, &DefaultScheme));
+            seen.extend(&group.members);
+        }
+
+        seen.sort();
+        assert_eq!(vec![0, 1, 2, 3, 4, 5], seen);
+    }
+
+    #[test]
+    fn impossible_partitions() {
+        // Every triple shares both 'a' and 'b'
+        let rucksacks = Rucksack::from_lines(&["abcd", "abef", "abgh"]);
+        assert_eq!(None, find_badge_groups(&rucksacks, 3));
+
+        // Not a multiple of the group size
+        let rucksacks = Rucksack::from_lines(&["ab", "ac", "ad", "ae"]);
+        assert_eq!(None, find_badge_groups(&rucksacks, 3));
+        assert_eq!(None, find_badge_groups(&rucksacks, 0));
+    }
+
+    #[test]
+    fn backtracks_past_greedy_choice() {
+        // Grouping the first rucksack with the second looks fine but strands the rest
+        let rucksacks = Rucksack::from_lines(&["ax", "ay", "bx", "cy"]);
+
+        let groups = find_badge_groups(&rucksacks, 2).unwrap();
+        assert_eq!(vec![0, 2], groups[0].members);
+        assert_eq!('x', groups[0].badge);
+        assert_eq!(vec![1, 3], groups[1].members);
+        assert_eq!('y', groups[1].badge);
+    }
+}
@@ -0,0 +1,125 @@
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{priority::PriorityScheme, GroupError, PartialGroup, Rucksack};
+
+/// Sum of every rucksack's misplaced item priority
+pub fn rucksack_total<S: PriorityScheme>(rucksacks: &[Rucksack<'_, S>]) -> u64 {
+    rucksacks
+        .iter()
+        .map(|rucksack| u64::from(rucksack.get_overlap_priority()))
+        .sum()
+}
+
+/// Sum of the badge priorities of every consecutive group of `group_size` rucksacks.
+///
+/// `partial` decides what happens when the rucksack count isn't a multiple of `group_size`.
+pub fn group_total<S: PriorityScheme>(
+    rucksacks: &[Rucksack<'_, S>],
+    group_size: usize,
+    partial: PartialGroup,
+) -> Result<u64, GroupError> {
+    let rucksacks = whole_groups(rucksacks, group_size, partial)?;
+
+    Ok(rucksacks
+        .chunks(group_size)
+        .map(|group| u64::from(Rucksack::get_group_overlap_priority(group)))
+        .sum())
+}
+
+/// Parallel version of `rucksack_total`. The total matches the sequential one exactly.
+#[cfg(feature = "rayon")]
+pub fn par_rucksack_total<S: PriorityScheme + Sync>(rucksacks: &[Rucksack<'_, S>]) -> u64 {
+    rucksacks
+        .par_iter()
+        .map(|rucksack| u64::from(rucksack.get_overlap_priority()))
+        .sum()
+}
+
+/// Parallel version of `group_total`. The total matches the sequential one exactly.
+#[cfg(feature = "rayon")]
+pub fn par_group_total<S: PriorityScheme + Sync>(
+    rucksacks: &[Rucksack<'_, S>],
+    group_size: usize,
+    partial: PartialGroup,
+) -> Result<u64, GroupError> {
+    let rucksacks = whole_groups(rucksacks, group_size, partial)?;
+
+    Ok(rucksacks
+        .par_chunks(group_size)
+        .map(|group| u64::from(Rucksack::get_group_overlap_priority(group)))
+        .sum())
+}
+
+/// The rucksacks that should be grouped, following the same rules as `groups_from_lines`
+fn whole_groups<T>(
+    rucksacks: &[T],
+    group_size: usize,
+    partial: PartialGroup,
+) -> Result<&[T], GroupError> {
+    if group_size == 0 {
+        return Err(GroupError::ZeroSize);
+    }
+
+    let leftover = rucksacks.len() % group_size;
+    match partial {
+        PartialGroup::Error if leftover > 0 => Err(GroupError::Incomplete {
+            expected: group_size,
+            found: leftover,
+        }),
+        PartialGroup::Drop => Ok(&rucksacks[..rucksacks.len() - leftover]),
+        _ => Ok(rucksacks),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const LINES: [&str; 7] = [
+        "vJrwpWtwJgWrhcsFMMfFFhFp",
+        "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+        "PmmdzqPrVvPwwTWBwg",
+        "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+        "ttgJtRGJQctTZtZT",
+        "CrZsJsPPZsGzwwsLwLmpwMDw",
+        "aa",
+    ];
+
+    #[test]
+    fn sequential_totals() {
+        let rucksacks = Rucksack::from_lines(&LINES);
+
+        assert_eq!(158, rucksack_total(&rucksacks));
+        assert_eq!(Ok(70), group_total(&rucksacks, 3, PartialGroup::Drop));
+        assert_eq!(Ok(71), group_total(&rucksacks, 3, PartialGroup::Keep));
+        assert_eq!(
+            Err(GroupError::Incomplete {
+                expected: 3,
+                found: 1
+            }),
+            group_total(&rucksacks, 3, PartialGroup::Error)
+        );
+        assert_eq!(
+            Err(GroupError::ZeroSize),
+            group_total(&rucksacks, 0, PartialGroup::Keep)
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_matches_sequential() {
+        let lines: Vec<&str> = LINES.iter().cycle().take(7 * 1000).copied().collect();
+        let rucksacks = Rucksack::from_lines(&lines);
+
+        assert_eq!(rucksack_total(&rucksacks), par_rucksack_total(&rucksacks));
+        for group_size in 1..=8 {
+            for partial in [PartialGroup::Error, PartialGroup::Drop, PartialGroup::Keep] {
+                assert_eq!(
+                    group_total(&rucksacks, group_size, partial),
+                    par_group_total(&rucksacks, group_size, partial)
+                );
+            }
+        }
+    }
+}
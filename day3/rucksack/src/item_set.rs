@@ -0,0 +1,282 @@
+use std::ops::{BitAnd, BitOr, BitXor, Sub};
+
+use crate::priority::{checked_priority, PriorityScheme, MAX_PRIORITY};
+
+/// A set of item types, stored as one bit per priority (bit 0 is priority 1).
+///
+/// Items are only named through a `PriorityScheme`, so every method that takes or yields a
+/// `char` is given the scheme to use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub fn new() -> Self {
+        ItemSet(0)
+    }
+
+    fn priority_bit(priority: u8) -> u64 {
+        assert!(
+            (1..=MAX_PRIORITY).contains(&priority),
+            "Unsupported priority: {priority}"
+        );
+        1 << (priority - 1)
+    }
+
+    /// Creates a set holding every item in `items`, or `None` if one isn't part of `scheme`
+    pub fn from_items(items: &str, scheme: &impl PriorityScheme) -> Option<Self> {
+        let mut set = ItemSet::new();
+        for item in items.chars() {
+            set.insert_priority(checked_priority(scheme, item)?);
+        }
+
+        Some(set)
+    }
+
+    /// Adds an item, returning whether it was newly added, or `None` if it isn't part of `scheme`
+    pub fn insert(&mut self, item: char, scheme: &impl PriorityScheme) -> Option<bool> {
+        checked_priority(scheme, item).map(|priority| self.insert_priority(priority))
+    }
+
+    /// Removes an item, returning whether it was present, or `None` if it isn't part of `scheme`
+    pub fn remove(&mut self, item: char, scheme: &impl PriorityScheme) -> Option<bool> {
+        let priority = checked_priority(scheme, item)?;
+        let was_present = self.contains_priority(priority);
+        self.0 &= !ItemSet::priority_bit(priority);
+
+        Some(was_present)
+    }
+
+    /// Whether the set holds `item`. Items that aren't part of `scheme` are never held.
+    pub fn contains(&self, item: char, scheme: &impl PriorityScheme) -> bool {
+        checked_priority(scheme, item).is_some_and(|priority| self.contains_priority(priority))
+    }
+
+    /// Adds the item with the given priority, returning whether it was newly added.
+    ///
+    /// Panics unless `priority` is from 1 to `MAX_PRIORITY`.
+    pub fn insert_priority(&mut self, priority: u8) -> bool {
+        let was_present = self.contains_priority(priority);
+        self.0 |= ItemSet::priority_bit(priority);
+
+        !was_present
+    }
+
+    /// Panics unless `priority` is from 1 to `MAX_PRIORITY`
+    pub fn contains_priority(&self, priority: u8) -> bool {
+        self.0 & ItemSet::priority_bit(priority) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn union(&self, other: &ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn intersection(&self, other: &ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    /// Items in this set that aren't in `other`
+    pub fn difference(&self, other: &ItemSet) -> ItemSet {
+        ItemSet(self.0 & !other.0)
+    }
+
+    /// Items in exactly one of the two sets
+    pub fn symmetric_difference(&self, other: &ItemSet) -> ItemSet {
+        ItemSet(self.0 ^ other.0)
+    }
+
+    pub fn is_subset(&self, other: &ItemSet) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    /// The lowest priority in the set
+    pub fn first_priority(&self) -> Option<u8> {
+        self.priorities().next()
+    }
+
+    /// Iterates over the priorities in the set, lowest first
+    pub fn priorities(&self) -> Priorities {
+        Priorities(self.0)
+    }
+
+    /// Iterates over the items as `scheme` names them, from lowest to highest priority.
+    ///
+    /// Priorities that `scheme` has no item for are skipped, so use a set's own scheme here
+    /// or go through `priorities` instead.
+    pub fn items_in<'s, S: PriorityScheme>(
+        &self,
+        scheme: &'s S,
+    ) -> impl Iterator<Item = char> + 's {
+        self.priorities()
+            .filter_map(move |priority| scheme.item(priority))
+    }
+
+    /// Sum of the priorities of every item in the set
+    pub fn priority_sum(&self) -> u32 {
+        self.priorities().map(u32::from).sum()
+    }
+}
+
+/// Iterator over the priorities of an `ItemSet` in ascending order
+pub struct Priorities(u64);
+
+impl Iterator for Priorities {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let bit = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+
+        Some(bit as u8 + 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl IntoIterator for ItemSet {
+    type Item = u8;
+    type IntoIter = Priorities;
+
+    fn into_iter(self) -> Priorities {
+        self.priorities()
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = ItemSet;
+
+    fn bitor(self, rhs: ItemSet) -> ItemSet {
+        self.union(&rhs)
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, rhs: ItemSet) -> ItemSet {
+        self.intersection(&rhs)
+    }
+}
+
+impl Sub for ItemSet {
+    type Output = ItemSet;
+
+    fn sub(self, rhs: ItemSet) -> ItemSet {
+        self.difference(&rhs)
+    }
+}
+
+impl BitXor for ItemSet {
+    type Output = ItemSet;
+
+    fn bitxor(self, rhs: ItemSet) -> ItemSet {
+        self.symmetric_difference(&rhs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::priority::{DefaultScheme, MappedScheme};
+
+    fn set(items: &str) -> ItemSet {
+        ItemSet::from_items(items, &DefaultScheme).unwrap()
+    }
+
+    #[test]
+    fn set_algebra() {
+        let a = set("abcZ");
+        let b = set("cdZ");
+
+        assert_eq!(set("abcdZ"), a | b);
+        assert_eq!(set("cZ"), a & b);
+        assert_eq!(set("ab"), a - b);
+        assert_eq!(set("abd"), a ^ b);
+        assert!((a & b).is_subset(&a));
+        assert!(!a.is_subset(&b));
+    }
+
+    #[test]
+    fn membership() {
+        let mut set = ItemSet::new();
+        assert!(set.is_empty());
+
+        assert_eq!(Some(true), set.insert('q', &DefaultScheme));
+        assert_eq!(Some(false), set.insert('q', &DefaultScheme));
+        assert_eq!(Some(true), set.insert('Q', &DefaultScheme));
+        assert!(set.contains('q', &DefaultScheme));
+        assert!(!set.contains('r', &DefaultScheme));
+        assert_eq!(2, set.len());
+
+        assert_eq!(Some(true), set.remove('q', &DefaultScheme));
+        assert_eq!(Some(false), set.remove('q', &DefaultScheme));
+        assert_eq!(1, set.len());
+    }
+
+    #[test]
+    fn unknown_items() {
+        let mut set = set("ab");
+
+        assert_eq!(None, ItemSet::from_items("ab1", &DefaultScheme));
+        assert_eq!(None, set.insert('é', &DefaultScheme));
+        assert_eq!(None, set.remove('#', &DefaultScheme));
+        assert!(!set.contains('1', &DefaultScheme));
+        assert_eq!(2, set.len());
+    }
+
+    #[test]
+    fn priority_order() {
+        let set = set("ZzAaPp");
+
+        assert_eq!(
+            vec!['a', 'p', 'z', 'A', 'P', 'Z'],
+            set.items_in(&DefaultScheme).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![1, 16, 26, 27, 42, 52],
+            set.into_iter().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(1), set.first_priority());
+        assert_eq!(1 + 16 + 26 + 27 + 42 + 52, set.priority_sum());
+    }
+
+    #[test]
+    fn other_schemes() {
+        let scheme = MappedScheme::from_mapping("0 1\n1 2\n# 53\né 64").unwrap();
+
+        let mut set = ItemSet::from_items("é#0é", &scheme).unwrap();
+        assert_eq!(3, set.len());
+        assert!(set.contains_priority(53));
+        assert!(set.contains('#', &scheme));
+        assert_eq!(Some(1), set.first_priority());
+        assert_eq!(vec![1, 53, 64], set.priorities().collect::<Vec<_>>());
+        assert_eq!(
+            vec!['0', '#', 'é'],
+            set.items_in(&scheme).collect::<Vec<_>>()
+        );
+        assert_eq!(1 + 53 + 64, set.priority_sum());
+
+        assert_eq!(Some(true), set.insert('1', &scheme));
+        assert_eq!(Some(true), set.remove('é', &scheme));
+        assert_eq!(
+            vec!['0', '1', '#'],
+            set.items_in(&scheme).collect::<Vec<_>>()
+        );
+
+        assert_eq!(None, ItemSet::from_items("01a", &scheme));
+    }
+}
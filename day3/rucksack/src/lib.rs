@@ -1,23 +1,117 @@
+use std::{
+    cmp::Reverse,
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+pub mod badges;
+pub mod batch;
+mod item_set;
+mod owned;
+pub mod priority;
+pub mod stats;
+pub mod validate;
+
+pub use item_set::ItemSet;
+pub use owned::{Groups, ReadError, RucksackBuf};
+use priority::{DefaultScheme, PriorityScheme, MAX_PRIORITY};
+
+/// What to do with leftover lines that don't fill a whole group
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PartialGroup {
+    /// Fail with `GroupError::Incomplete`
+    Error,
+    /// Silently ignore the leftover lines
+    Drop,
+    /// Return the leftover lines as a smaller final group
+    Keep,
+}
+
+/// Problems found while splitting rucksacks into groups
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum GroupError {
+    /// Groups must hold at least one rucksack
+    ZeroSize,
+    /// The last group only had `found` of the `expected` rucksacks
+    Incomplete { expected: usize, found: usize },
+}
+
+impl Display for GroupError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            GroupError::ZeroSize => write!(f, "group size must be at least 1"),
+            GroupError::Incomplete { expected, found } => {
+                write!(f, "last group has {found} rucksack(s), expected {expected}")
+            }
+        }
+    }
+}
+
+impl Error for GroupError {}
+
+/// Problems with the contents of a single Rucksack
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ContentsError {
+    /// The items can't be split into two equal compartments
+    OddLength(usize),
+    /// An item isn't part of the priority scheme. `column` is 1-based and counts characters.
+    UnknownItem { item: char, column: usize },
+    /// The scheme gave an item a priority outside 1 to `MAX_PRIORITY`
+    BadPriority {
+        item: char,
+        priority: u8,
+        column: usize,
+    },
+}
+
+impl Display for ContentsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            ContentsError::OddLength(count) => {
+                write!(f, "{count} item(s) can't be split into two compartments")
+            }
+            ContentsError::UnknownItem { item, column } => {
+                write!(f, "column {column}: unknown item '{item}'")
+            }
+            ContentsError::BadPriority {
+                item,
+                priority,
+                column,
+            } => write!(
+                f,
+                "column {column}: item '{item}' has priority {priority}, outside 1 to {MAX_PRIORITY}"
+            ),
+        }
+    }
+}
+
+impl Error for ContentsError {}
+
 #[derive(Debug)]
-pub struct Rucksack<'a> {
+pub struct Rucksack<'a, S = DefaultScheme> {
     full_contents: &'a str,
     compartments: (&'a str, &'a str),
     count: usize,
+    scheme: &'a S,
 }
 
 impl<'a> Rucksack<'a> {
-    /// Creates a new Rucksack from an input string
+    /// Creates a new Rucksack from an input string.
+    ///
+    /// Panics if the contents aren't valid under `DefaultScheme`. Use `parse` to handle that.
     pub fn from_string(item_str: &'a str) -> Self {
-        let compartments = item_str.split_at(item_str.len() / 2);
-
-        Rucksack {
-            full_contents: item_str,
-            compartments,
-            count: item_str.len(),
+        match Rucksack::parse(item_str) {
+            Ok(rucksack) => rucksack,
+            Err(err) => panic!("Invalid rucksack '{item_str}': {err}"),
         }
     }
 
-    pub fn from_lines(item_lines: &Vec<&'a str>) -> Vec<Self> {
+    /// Creates a new Rucksack using `DefaultScheme`
+    pub fn parse(item_str: &'a str) -> Result<Self, ContentsError> {
+        Rucksack::parse_with(item_str, &DefaultScheme)
+    }
+
+    pub fn from_lines(item_lines: &[&'a str]) -> Vec<Self> {
         let mut rucksacks: Vec<Rucksack> = Vec::new();
 
         for line in item_lines {
@@ -27,117 +121,167 @@ impl<'a> Rucksack<'a> {
         rucksacks
     }
 
-    pub fn groups_from_lines(item_lines: &Vec<&'a str>) -> Vec<[Self; 3]> {
+    /// Splits consecutive lines into groups of `group_size` Rucksacks.
+    ///
+    /// Each group is ordered from the fullest to the emptiest Rucksack. `partial` decides what
+    /// happens when the line count isn't a multiple of `group_size`.
+    pub fn groups_from_lines(
+        item_lines: &[&'a str],
+        group_size: usize,
+        partial: PartialGroup,
+    ) -> Result<Vec<Vec<Self>>, GroupError> {
+        if group_size == 0 {
+            return Err(GroupError::ZeroSize);
+        }
+
         let mut groups = Vec::new();
 
-        for i in (0..item_lines.len()).step_by(3) {
-            let mut group = [
-                Rucksack::from_string(item_lines[i]),
-                Rucksack::from_string(item_lines[i + 1]),
-                Rucksack::from_string(item_lines[i + 2]),
-            ];
+        for chunk in item_lines.chunks(group_size) {
+            if chunk.len() < group_size {
+                match partial {
+                    PartialGroup::Error => {
+                        return Err(GroupError::Incomplete {
+                            expected: group_size,
+                            found: chunk.len(),
+                        })
+                    }
+                    PartialGroup::Drop => break,
+                    PartialGroup::Keep => {}
+                }
+            }
 
-            group.sort_by(|a, b| b.count.cmp(&a.count));
+            let mut group = Rucksack::from_lines(chunk);
+            group.sort_by_key(|rucksack| Reverse(rucksack.count));
 
             groups.push(group);
         }
 
-        groups
+        Ok(groups)
     }
+}
 
-    /// Creates a string from the items that are present in both Rucksack compartments
-    pub fn get_overlap(&self) -> Option<char> {
-        for i in 0..self.compartments.0.len() {
-            let char0 = self.compartments.0.chars().nth(i).unwrap();
-
-            if self.compartments.1.contains(char0) {
-                return Some(char0);
+impl<'a, S: PriorityScheme> Rucksack<'a, S> {
+    /// Creates a new Rucksack whose items are prioritised by `scheme`.
+    ///
+    /// The compartments are split by character, not byte, so multibyte items are kept whole.
+    pub fn parse_with(item_str: &'a str, scheme: &'a S) -> Result<Self, ContentsError> {
+        let mut count = 0;
+        for (idx, item) in item_str.chars().enumerate() {
+            match scheme.priority(item) {
+                None => {
+                    return Err(ContentsError::UnknownItem {
+                        item,
+                        column: idx + 1,
+                    })
+                }
+                Some(priority) if !(1..=MAX_PRIORITY).contains(&priority) => {
+                    return Err(ContentsError::BadPriority {
+                        item,
+                        priority,
+                        column: idx + 1,
+                    })
+                }
+                Some(_) => {}
             }
+            count += 1;
+        }
+
+        if count % 2 != 0 {
+            return Err(ContentsError::OddLength(count));
         }
 
-        None
+        let middle = item_str
+            .char_indices()
+            .nth(count / 2)
+            .map_or(item_str.len(), |(idx, _)| idx);
+
+        Ok(Rucksack {
+            full_contents: item_str,
+            compartments: item_str.split_at(middle),
+            count,
+            scheme,
+        })
     }
 
-    pub fn get_overlap_priority(&self) -> u8 {
-        match self.get_overlap() {
-            Some(c) => priority_of_char(&c),
-            None => 0,
-        }
+    /// The scheme used to prioritise the Rucksack's items
+    pub fn scheme(&self) -> &'a S {
+        self.scheme
     }
 
-    pub fn get_group_overlap(group: &[Rucksack<'a>; 3]) -> Option<char> {
-        for c in group[0].full_contents.chars() {
-            if group[1].full_contents.contains(c) && group[2].full_contents.contains(c) {
-                return Some(c);
-            }
-        }
+    /// Number of items in the Rucksack
+    pub fn len(&self) -> usize {
+        self.count
+    }
 
-        None
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
     }
 
-    pub fn get_group_overlap_priority(group: &[Rucksack<'a>; 3]) -> u8 {
-        match Rucksack::get_group_overlap(group) {
-            Some(c) => priority_of_char(&c),
-            None => 0,
-        }
+    fn item_set(&self, items: &str) -> ItemSet {
+        ItemSet::from_items(items, self.scheme).expect("items were checked by parse_with")
     }
-}
 
-fn priority_of_char(c: &char) -> u8 {
-    let mut priority: u8 = match c.to_ascii_lowercase() {
-        'a' => 1,
-        'b' => 2,
-        'c' => 3,
-        'd' => 4,
-        'e' => 5,
-        'f' => 6,
-        'g' => 7,
-        'h' => 8,
-        'i' => 9,
-        'j' => 10,
-        'k' => 11,
-        'l' => 12,
-        'm' => 13,
-        'n' => 14,
-        'o' => 15,
-        'p' => 16,
-        'q' => 17,
-        'r' => 18,
-        's' => 19,
-        't' => 20,
-        'u' => 21,
-        'v' => 22,
-        'w' => 23,
-        'x' => 24,
-        'y' => 25,
-        'z' => 26,
-        val => {
-            panic!("Unsupported character: \'{val}\'");
-        }
-    };
-
-    if c.is_ascii_uppercase() {
-        priority += 26;
-    }
-
-    priority
-}
+    /// Every item type in the Rucksack
+    pub fn items(&self) -> ItemSet {
+        self.item_set(self.full_contents)
+    }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    /// The item types held in each compartment
+    pub fn compartment_items(&self) -> (ItemSet, ItemSet) {
+        (
+            self.item_set(self.compartments.0),
+            self.item_set(self.compartments.1),
+        )
+    }
+
+    /// Every item type that is present in both Rucksack compartments
+    pub fn shared_items(&self) -> ItemSet {
+        let (first, second) = self.compartment_items();
+        first & second
+    }
+
+    /// The lowest priority item that is present in both Rucksack compartments
+    pub fn get_overlap(&self) -> Option<char> {
+        self.shared_items()
+            .first_priority()
+            .and_then(|priority| self.scheme.item(priority))
+    }
 
-    #[test]
-    fn char_priority() {
-        let input_data: Vec<(char, u8)> = vec![('a', 1), ('A', 27)];
+    pub fn get_overlap_priority(&self) -> u8 {
+        self.shared_items().first_priority().unwrap_or(0)
+    }
 
-        for data in input_data {
-            assert_eq!(data.1, priority_of_char(&data.0));
+    /// Every item type carried by all members of the group. Empty for an empty group.
+    pub fn group_shared_items(group: &[Rucksack<'a, S>]) -> ItemSet {
+        match group.split_first() {
+            Some((first, rest)) => rest
+                .iter()
+                .fold(first.items(), |shared, rucksack| shared & rucksack.items()),
+            None => ItemSet::new(),
         }
     }
 
+    pub fn get_group_overlap(group: &[Rucksack<'a, S>]) -> Option<char> {
+        let scheme = group.first()?.scheme;
+        Rucksack::group_shared_items(group)
+            .first_priority()
+            .and_then(|priority| scheme.item(priority))
+    }
+
+    pub fn get_group_overlap_priority(group: &[Rucksack<'a, S>]) -> u8 {
+        Rucksack::group_shared_items(group)
+            .first_priority()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod test {
     mod rucksack {
-        use crate::Rucksack;
+        use crate::{
+            priority::{DefaultScheme, MappedScheme, PriorityScheme},
+            ContentsError, GroupError, ItemSet, PartialGroup, Rucksack,
+        };
 
         #[test]
         fn equal_split() {
@@ -186,11 +330,152 @@ mod test {
                 'r',
             );
 
-            let rucksacks = Rucksack::groups_from_lines(&input_data.0);
+            let rucksacks =
+                Rucksack::groups_from_lines(&input_data.0, 3, PartialGroup::Error).unwrap();
             assert_eq!(
                 Some(input_data.1),
                 Rucksack::get_group_overlap(&rucksacks[0])
             );
         }
+
+        #[test]
+        fn shared_items() {
+            let rucksack = Rucksack::from_string("abcXYZcbaXAB");
+
+            assert_eq!(
+                ItemSet::from_items("abcXYZ", &DefaultScheme).unwrap(),
+                rucksack.compartment_items().0
+            );
+            assert_eq!(
+                ItemSet::from_items("abcX", &DefaultScheme).unwrap(),
+                rucksack.shared_items()
+            );
+            assert_eq!(Some('a'), rucksack.get_overlap());
+        }
+
+        #[test]
+        fn group_shared_items() {
+            let group = Rucksack::groups_from_lines(
+                &["abcdXY", "bcdeYZ", "cdefYX"],
+                3,
+                PartialGroup::Error,
+            )
+            .unwrap();
+
+            assert_eq!(
+                ItemSet::from_items("cdY", &DefaultScheme).unwrap(),
+                Rucksack::group_shared_items(&group[0])
+            );
+        }
+
+        #[test]
+        fn group_sizes() {
+            let lines = ["ab", "bc", "bd", "be", "bf"];
+
+            let groups = Rucksack::groups_from_lines(&lines, 2, PartialGroup::Drop).unwrap();
+            assert_eq!(2, groups.len());
+
+            let groups = Rucksack::groups_from_lines(&lines, 2, PartialGroup::Keep).unwrap();
+            assert_eq!(
+                vec![2, 2, 1],
+                groups.iter().map(Vec::len).collect::<Vec<_>>()
+            );
+            assert_eq!(Some('b'), Rucksack::get_group_overlap(&groups[2]));
+
+            assert_eq!(
+                Err(GroupError::Incomplete {
+                    expected: 2,
+                    found: 1
+                }),
+                Rucksack::groups_from_lines(&lines, 2, PartialGroup::Error).map(|_| ())
+            );
+            assert_eq!(
+                Err(GroupError::ZeroSize),
+                Rucksack::groups_from_lines(&lines, 0, PartialGroup::Keep).map(|_| ())
+            );
+
+            let groups = Rucksack::groups_from_lines(&lines, 5, PartialGroup::Error).unwrap();
+            assert_eq!(Some('b'), Rucksack::get_group_overlap(&groups[0]));
+            assert_eq!(None, Rucksack::get_group_overlap(&[] as &[Rucksack]));
+        }
+
+        #[test]
+        fn multibyte_items() {
+            let scheme = MappedScheme::from_mapping("é 1\nß 2\n1 3\n€ 4").unwrap();
+            let rucksack = Rucksack::parse_with("é1ßß€é", &scheme).unwrap();
+
+            assert_eq!(("é1ß", "ß€é"), rucksack.compartments);
+            assert_eq!(6, rucksack.len());
+            assert_eq!(Some('é'), rucksack.get_overlap());
+            assert_eq!(1, rucksack.get_overlap_priority());
+
+            let group = [rucksack, Rucksack::parse_with("€€1ß", &scheme).unwrap()];
+            assert_eq!(Some('ß'), Rucksack::get_group_overlap(&group));
+            assert_eq!(2, Rucksack::get_group_overlap_priority(&group));
+        }
+
+        #[test]
+        fn invalid_contents() {
+            assert_eq!(
+                ContentsError::OddLength(3),
+                Rucksack::parse("abc").unwrap_err()
+            );
+            assert_eq!(
+                ContentsError::UnknownItem {
+                    item: 'é',
+                    column: 3
+                },
+                Rucksack::parse("abéd").unwrap_err()
+            );
+            assert!(Rucksack::parse("").unwrap().is_empty());
+
+            let scheme = MappedScheme::from_mapping("é 1").unwrap();
+            assert_eq!(
+                ContentsError::OddLength(1),
+                Rucksack::parse_with("é", &scheme).unwrap_err()
+            );
+        }
+
+        /// Gives 'a' to 'z' priorities from 0 up, so the ends fall outside 1 to `MAX_PRIORITY`
+        #[derive(Debug)]
+        struct Stretched;
+
+        impl PriorityScheme for Stretched {
+            fn priority(&self, item: char) -> Option<u8> {
+                item.is_ascii_lowercase().then(|| (item as u8 - b'a') * 3)
+            }
+
+            fn item(&self, _priority: u8) -> Option<char> {
+                None
+            }
+        }
+
+        #[test]
+        fn priorities_out_of_range() {
+            assert_eq!(
+                ContentsError::BadPriority {
+                    item: 'a',
+                    priority: 0,
+                    column: 2
+                },
+                Rucksack::parse_with("ba", &Stretched).unwrap_err()
+            );
+            assert_eq!(
+                ContentsError::BadPriority {
+                    item: 'y',
+                    priority: 72,
+                    column: 1
+                },
+                Rucksack::parse_with("yb", &Stretched).unwrap_err()
+            );
+            assert!(Rucksack::parse_with("bv", &Stretched).is_ok());
+
+            let mut set = ItemSet::from_items("bc", &Stretched).unwrap();
+            assert_eq!(None, ItemSet::from_items("bz", &Stretched));
+            assert_eq!(None, set.insert('z', &Stretched));
+            assert_eq!(None, set.remove('a', &Stretched));
+            assert!(!set.contains('z', &Stretched));
+            assert_eq!(2, set.len());
+        }
     }
 }
@@ -1,15 +1,233 @@
-use rucksack::Rucksack;
-use std::fs;
+use clap::{value_parser, Arg, ArgAction, Command};
+use rucksack::{
+    priority::{DefaultScheme, MappedScheme, PriorityScheme},
+    validate::{Report, Validator},
+    GroupError, Groups, PartialGroup, ReadError, Rucksack, RucksackBuf,
+};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    process::ExitCode,
+};
 
-fn main() {
-    let input_data = fs::read_to_string("input.txt").expect("Failed to read input file");
-    let rucksacks = Rucksack::groups_from_lines(&input_data.lines().collect());
+/// Which totals to calculate
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Part {
+    /// Priority of the item shared by each rucksack's compartments
+    Compartments,
+    /// Priority of each group's badge
+    Badges,
+    Both,
+}
+
+#[derive(Debug, Default)]
+struct Totals {
+    rucksacks: usize,
+    groups: usize,
+    compartments: u64,
+    badges: u64,
+    /// Set when the last group was short, so the badge total is unusable
+    incomplete: Option<GroupError>,
+}
+
+/// Open the input file, or stdin when no path (or `-`) is given
+fn open_input(path: Option<&String>) -> io::Result<Box<dyn BufRead>> {
+    match path.map(String::as_str) {
+        None | Some("-") => Ok(Box::new(io::stdin().lock())),
+        Some(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+    }
+}
+
+fn score<S: PriorityScheme + Clone>(
+    input: impl BufRead,
+    scheme: S,
+    group_size: usize,
+) -> Result<Totals, ReadError> {
+    // Keep a short last group so its rucksacks still count towards the compartment total
+    let groups = Groups::with_scheme(input, group_size, PartialGroup::Keep, scheme)
+        .map_err(ReadError::Group)?;
+
+    let mut totals = Totals::default();
+    for group in groups {
+        let group = group?;
+        let rucksacks: Vec<Rucksack<S>> = group.iter().map(RucksackBuf::as_rucksack).collect();
+
+        totals.rucksacks += rucksacks.len();
+        totals.compartments += rucksacks
+            .iter()
+            .map(|rucksack| u64::from(rucksack.get_overlap_priority()))
+            .sum::<u64>();
+
+        if rucksacks.len() < group_size {
+            totals.incomplete = Some(GroupError::Incomplete {
+                expected: group_size,
+                found: rucksacks.len(),
+            });
+        } else {
+            totals.groups += 1;
+            totals.badges += u64::from(Rucksack::get_group_overlap_priority(&rucksacks));
+        }
+    }
+
+    Ok(totals)
+}
+
+/// Check every rucksack, and every group unless only compartments are wanted
+fn validate<S: PriorityScheme>(
+    input: impl BufRead,
+    scheme: &S,
+    group_size: Option<usize>,
+) -> Result<Report, ReadError> {
+    let lines = input.lines().collect::<io::Result<Vec<String>>>()?;
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+
+    let mut validator = Validator::new(scheme);
+    if let Some(group_size) = group_size {
+        validator = validator.group_size(group_size).map_err(ReadError::Group)?;
+    }
+
+    Ok(validator.validate(&lines))
+}
+
+fn main() -> ExitCode {
+    let cfg = Command::new("Rucksack Reorganizer")
+        .author("Brenden Davidson")
+        .version("0.1.0")
+        .about("Advent of Code 2022: Day 3 solution")
+        .args([
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .action(ArgAction::Set)
+                .help("the input file to use, reads stdin when missing or '-'"),
+            Arg::new("part")
+                .short('p')
+                .long("part")
+                .action(ArgAction::Set)
+                .value_parser(["1", "2", "both"])
+                .default_value("both")
+                .help("1 totals compartment priorities, 2 totals group badge priorities"),
+            Arg::new("group-size")
+                .short('g')
+                .long("group-size")
+                .action(ArgAction::Set)
+                .value_parser(value_parser!(u64).range(1..))
+                .default_value("3")
+                .help("number of rucksacks in each group"),
+            Arg::new("scheme")
+                .short('s')
+                .long("scheme")
+                .action(ArgAction::Set)
+                .help("a file of '<item> <priority>' lines to use instead of a-z, A-Z"),
+            Arg::new("json")
+                .short('j')
+                .long("json")
+                .action(ArgAction::SetTrue)
+                .help("also write the totals as a JSON object after the summary"),
+            Arg::new("validate")
+                .long("validate")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("json")
+                .help("report invalid rucksacks and groups instead of totalling them"),
+        ])
+        .get_matches();
+
+    let input_path = cfg.get_one::<String>("input");
+    let part = match cfg.get_one::<String>("part").unwrap().as_str() {
+        "1" => Part::Compartments,
+        "2" => Part::Badges,
+        _ => Part::Both,
+    };
+    let group_size = *cfg.get_one::<u64>("group-size").unwrap() as usize;
+
+    let input = match open_input(input_path) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!(
+                "error: failed to open '{}': {err}",
+                input_path.map_or("-", String::as_str)
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if cfg.get_flag("validate") {
+        let group_size = Some(group_size).filter(|_| part != Part::Compartments);
+        let report = match cfg.get_one::<String>("scheme") {
+            None => validate(input, &DefaultScheme, group_size),
+            Some(path) => match MappedScheme::from_file(path) {
+                Ok(scheme) => validate(input, &scheme, group_size),
+                Err(err) => {
+                    eprintln!("error: '{path}': {err}");
+                    return ExitCode::FAILURE;
+                }
+            },
+        };
+
+        return match report {
+            Ok(report) => {
+                println!("{report}");
+                if report.is_valid() {
+                    ExitCode::SUCCESS
+                } else {
+                    ExitCode::FAILURE
+                }
+            }
+            Err(err) => {
+                eprintln!("error: {err}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let totals = match cfg.get_one::<String>("scheme") {
+        None => score(input, DefaultScheme, group_size),
+        Some(path) => match MappedScheme::from_file(path) {
+            Ok(scheme) => score(input, &scheme, group_size),
+            Err(err) => {
+                eprintln!("error: '{path}': {err}");
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+    let totals = match totals {
+        Ok(totals) => totals,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let show_compartments = part != Part::Badges;
+    // Badges can't be totalled when the last group is short
+    let badge_error = totals.incomplete.filter(|_| part != Part::Compartments);
+    let show_badges = part != Part::Compartments && badge_error.is_none();
+
+    println!("Rucksacks: {}", totals.rucksacks);
+    if show_compartments {
+        println!("Compartment priority total: {}", totals.compartments);
+    }
+    if show_badges {
+        println!("Groups: {}", totals.groups);
+        println!("Badge priority total: {}", totals.badges);
+    }
+
+    if cfg.get_flag("json") {
+        let mut fields = vec![format!("\"rucksacks\":{}", totals.rucksacks)];
+        if show_compartments {
+            fields.push(format!("\"compartment_total\":{}", totals.compartments));
+        }
+        if show_badges {
+            fields.push(format!("\"groups\":{}", totals.groups));
+            fields.push(format!("\"badge_total\":{}", totals.badges));
+        }
+        println!("{{{}}}", fields.join(","));
+    }
 
-    println!("Rucksack count: {}", rucksacks.len());
-    let mut total_priority: u64 = 0;
-    for group in rucksacks {
-        total_priority += u64::from(Rucksack::get_group_overlap_priority(&group));
+    if let Some(err) = badge_error {
+        eprintln!("error: {err}");
+        return ExitCode::FAILURE;
     }
 
-    println!("Total priority: \'{}\'", total_priority);
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,378 @@
+use std::{
+    cmp::Reverse,
+    error::Error,
+    fmt::{Display, Formatter},
+    io::{self, BufRead},
+};
+
+use crate::{
+    priority::{DefaultScheme, PriorityScheme},
+    ContentsError, GroupError, PartialGroup, Rucksack,
+};
+
+/// Problems reading rucksacks from a reader or line iterator
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    /// Bad contents on the given 1-based line
+    Contents {
+        line: usize,
+        error: ContentsError,
+    },
+    Group(GroupError),
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            ReadError::Io(err) => write!(f, "failed to read rucksacks: {err}"),
+            ReadError::Contents { line, error } => write!(f, "line {line}: {error}"),
+            ReadError::Group(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReadError::Io(err) => Some(err),
+            ReadError::Contents { error, .. } => Some(error),
+            ReadError::Group(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(err: io::Error) -> Self {
+        ReadError::Io(err)
+    }
+}
+
+/// A Rucksack that owns its contents, so it can outlive the input it was read from.
+///
+/// Use `as_rucksack` to run the `Rucksack` queries on it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RucksackBuf<S = DefaultScheme> {
+    contents: String,
+    /// Byte offset of the second compartment
+    middle: usize,
+    count: usize,
+    scheme: S,
+}
+
+impl RucksackBuf {
+    /// Creates a new RucksackBuf using `DefaultScheme`
+    pub fn parse(contents: impl Into<String>) -> Result<Self, ContentsError> {
+        RucksackBuf::parse_with(contents, DefaultScheme)
+    }
+
+    /// Parses every line using `DefaultScheme`
+    pub fn from_lines<I>(lines: I) -> Result<Vec<Self>, ReadError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        RucksackBuf::from_lines_with(lines, DefaultScheme)
+    }
+
+    /// Reads every line of `reader` using `DefaultScheme`
+    pub fn from_reader(reader: impl BufRead) -> Result<Vec<Self>, ReadError> {
+        RucksackBuf::from_reader_with(reader, DefaultScheme)
+    }
+}
+
+impl<S: PriorityScheme> RucksackBuf<S> {
+    /// Creates a new RucksackBuf whose items are prioritised by `scheme`.
+    ///
+    /// Pass `&scheme` to share one scheme between many rucksacks.
+    pub fn parse_with(contents: impl Into<String>, scheme: S) -> Result<Self, ContentsError> {
+        let contents = contents.into();
+        let (middle, count) = {
+            let rucksack = Rucksack::parse_with(&contents, &scheme)?;
+            (rucksack.compartments.0.len(), rucksack.len())
+        };
+
+        Ok(RucksackBuf {
+            contents,
+            middle,
+            count,
+            scheme,
+        })
+    }
+
+    pub fn from_lines_with<I>(lines: I, scheme: S) -> Result<Vec<Self>, ReadError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+        S: Clone,
+    {
+        lines
+            .into_iter()
+            .enumerate()
+            .map(|(idx, line)| parse_line(line.as_ref(), idx + 1, scheme.clone()))
+            .collect()
+    }
+
+    pub fn from_reader_with(reader: impl BufRead, scheme: S) -> Result<Vec<Self>, ReadError>
+    where
+        S: Clone,
+    {
+        let mut rucksacks = Vec::new();
+        for (idx, line) in reader.lines().enumerate() {
+            rucksacks.push(parse_line(&line?, idx + 1, scheme.clone())?);
+        }
+
+        Ok(rucksacks)
+    }
+
+    /// Borrows the RucksackBuf as a `Rucksack`
+    pub fn as_rucksack(&self) -> Rucksack<'_, S> {
+        Rucksack {
+            full_contents: &self.contents,
+            compartments: self.contents.split_at(self.middle),
+            count: self.count,
+            scheme: &self.scheme,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.contents
+    }
+
+    pub fn into_string(self) -> String {
+        self.contents
+    }
+
+    /// Number of items in the Rucksack
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+}
+
+impl<S: PriorityScheme + Clone> Rucksack<'_, S> {
+    /// Copies the Rucksack's contents into a `RucksackBuf`
+    pub fn to_buf(&self) -> RucksackBuf<S> {
+        RucksackBuf {
+            contents: self.full_contents.to_string(),
+            middle: self.compartments.0.len(),
+            count: self.count,
+            scheme: self.scheme.clone(),
+        }
+    }
+}
+
+fn parse_line<S: PriorityScheme>(
+    line: &str,
+    number: usize,
+    scheme: S,
+) -> Result<RucksackBuf<S>, ReadError> {
+    RucksackBuf::parse_with(line, scheme).map_err(|error| ReadError::Contents {
+        line: number,
+        error,
+    })
+}
+
+/// Reads groups of rucksacks as lines arrive, so the whole inventory never has to be held.
+///
+/// Like `Rucksack::groups_from_lines`, each group is ordered from the fullest to the emptiest
+/// Rucksack. The iterator stops after the first error.
+pub struct Groups<R, S = DefaultScheme> {
+    lines: io::Lines<R>,
+    lines_read: usize,
+    group_size: usize,
+    partial: PartialGroup,
+    scheme: S,
+    done: bool,
+}
+
+impl<R: BufRead> Groups<R> {
+    /// Groups the lines of `reader` using `DefaultScheme`
+    pub fn new(reader: R, group_size: usize, partial: PartialGroup) -> Result<Self, GroupError> {
+        Groups::with_scheme(reader, group_size, partial, DefaultScheme)
+    }
+}
+
+impl<R: BufRead, S: PriorityScheme + Clone> Groups<R, S> {
+    pub fn with_scheme(
+        reader: R,
+        group_size: usize,
+        partial: PartialGroup,
+        scheme: S,
+    ) -> Result<Self, GroupError> {
+        if group_size == 0 {
+            return Err(GroupError::ZeroSize);
+        }
+
+        Ok(Groups {
+            lines: reader.lines(),
+            lines_read: 0,
+            group_size,
+            partial,
+            scheme,
+            done: false,
+        })
+    }
+
+    /// Number of lines read so far
+    pub fn lines_read(&self) -> usize {
+        self.lines_read
+    }
+
+    fn read_group(&mut self) -> Result<Option<Vec<RucksackBuf<S>>>, ReadError> {
+        let mut group = Vec::with_capacity(self.group_size);
+
+        while group.len() < self.group_size {
+            let line = match self.lines.next() {
+                Some(line) => line?,
+                None => break,
+            };
+
+            self.lines_read += 1;
+            group.push(parse_line(&line, self.lines_read, self.scheme.clone())?);
+        }
+
+        if group.is_empty() {
+            return Ok(None);
+        }
+        if group.len() < self.group_size {
+            match self.partial {
+                PartialGroup::Error => {
+                    return Err(ReadError::Group(GroupError::Incomplete {
+                        expected: self.group_size,
+                        found: group.len(),
+                    }))
+                }
+                PartialGroup::Drop => return Ok(None),
+                PartialGroup::Keep => {}
+            }
+        }
+
+        group.sort_by_key(|rucksack| Reverse(rucksack.count));
+        Ok(Some(group))
+    }
+}
+
+impl<R: BufRead, S: PriorityScheme + Clone> Iterator for Groups<R, S> {
+    type Item = Result<Vec<RucksackBuf<S>>, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let group = self.read_group();
+        if !matches!(group, Ok(Some(_))) {
+            self.done = true;
+        }
+
+        group.transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::priority::MappedScheme;
+
+    const INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
+    fn badge_priorities<R: BufRead>(groups: Groups<R>) -> Vec<u8> {
+        groups
+            .map(|group| {
+                let group = group.unwrap();
+                let group: Vec<Rucksack> = group.iter().map(RucksackBuf::as_rucksack).collect();
+                Rucksack::get_group_overlap_priority(&group)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn owned_matches_borrowed() {
+        let lines: Vec<String> = INPUT.lines().map(str::to_string).collect();
+        let owned = RucksackBuf::from_lines(&lines).unwrap();
+        drop(lines);
+
+        let read = RucksackBuf::from_reader(INPUT.as_bytes()).unwrap();
+        assert_eq!(owned, read);
+
+        for (buf, line) in owned.iter().zip(INPUT.lines()) {
+            let borrowed = Rucksack::from_string(line);
+
+            assert_eq!(line, buf.as_str());
+            assert_eq!(borrowed.get_overlap(), buf.as_rucksack().get_overlap());
+            assert_eq!(&borrowed.to_buf(), buf);
+        }
+    }
+
+    #[test]
+    fn shared_scheme() {
+        let scheme = MappedScheme::from_mapping("é 1\nß 2").unwrap();
+        let rucksacks = RucksackBuf::from_lines_with(["éßéé", "ßßéß"], &scheme).unwrap();
+
+        assert_eq!(Some('é'), rucksacks[0].as_rucksack().get_overlap());
+        assert_eq!(Some('ß'), rucksacks[1].as_rucksack().get_overlap());
+        assert_eq!("éß", rucksacks[0].as_rucksack().compartments.0);
+    }
+
+    #[test]
+    fn line_errors() {
+        match RucksackBuf::from_reader("abab\nabc\n".as_bytes()) {
+            Err(ReadError::Contents { line, error }) => {
+                assert_eq!(2, line);
+                assert_eq!(ContentsError::OddLength(3), error);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn streamed_groups() {
+        let groups = Groups::new(INPUT.as_bytes(), 3, PartialGroup::Error).unwrap();
+        assert_eq!(vec![18, 52], badge_priorities(groups));
+
+        let groups = Groups::new(INPUT.as_bytes(), 4, PartialGroup::Drop).unwrap();
+        assert_eq!(1, badge_priorities(groups).len());
+
+        let groups = Groups::new(INPUT.as_bytes(), 4, PartialGroup::Keep).unwrap();
+        assert_eq!(2, badge_priorities(groups).len());
+
+        assert!(matches!(
+            Groups::new(INPUT.as_bytes(), 0, PartialGroup::Keep),
+            Err(GroupError::ZeroSize)
+        ));
+    }
+
+    #[test]
+    fn streamed_errors() {
+        let mut groups = Groups::new(INPUT.as_bytes(), 4, PartialGroup::Error).unwrap();
+
+        assert!(groups.next().unwrap().is_ok());
+        assert_eq!(4, groups.lines_read());
+        assert!(matches!(
+            groups.next(),
+            Some(Err(ReadError::Group(GroupError::Incomplete {
+                expected: 4,
+                found: 2
+            })))
+        ));
+        assert!(groups.next().is_none());
+
+        let mut groups = Groups::new("ab\nabc\nab\n".as_bytes(), 1, PartialGroup::Error).unwrap();
+        assert!(groups.next().unwrap().is_ok());
+        assert!(matches!(
+            groups.next(),
+            Some(Err(ReadError::Contents { line: 2, .. }))
+        ));
+        assert!(groups.next().is_none());
+    }
+}
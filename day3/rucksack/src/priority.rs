@@ -0,0 +1,277 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Formatter},
+    fs, io,
+    path::Path,
+};
+
+/// The highest priority an item can have, since `ItemSet` holds one bit per priority
+pub const MAX_PRIORITY: u8 = 64;
+
+/// Maps item types to priorities and back.
+///
+/// Priorities must run from 1 to `MAX_PRIORITY`. Items given any other priority are reported
+/// by `Rucksack::parse_with`, and treated as unknown by `ItemSet`.
+pub trait PriorityScheme {
+    /// The priority of `item`, or `None` if it isn't part of the alphabet
+    fn priority(&self, item: char) -> Option<u8>;
+
+    /// The item with the given priority, if there is one
+    fn item(&self, priority: u8) -> Option<char>;
+}
+
+impl<S: PriorityScheme + ?Sized> PriorityScheme for &S {
+    fn priority(&self, item: char) -> Option<u8> {
+        (**self).priority(item)
+    }
+
+    fn item(&self, priority: u8) -> Option<char> {
+        (**self).item(priority)
+    }
+}
+
+/// The priority `scheme` gives `item`, if it has one from 1 to `MAX_PRIORITY`
+pub(crate) fn checked_priority(scheme: &impl PriorityScheme, item: char) -> Option<u8> {
+    scheme
+        .priority(item)
+        .filter(|priority| (1..=MAX_PRIORITY).contains(priority))
+}
+
+/// The puzzle's scheme: 'a' through 'z' are 1 to 26 and 'A' through 'Z' are 27 to 52
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct DefaultScheme;
+
+impl PriorityScheme for DefaultScheme {
+    fn priority(&self, item: char) -> Option<u8> {
+        match item {
+            'a'..='z' => Some(item as u8 - b'a' + 1),
+            'A'..='Z' => Some(item as u8 - b'A' + 27),
+            _ => None,
+        }
+    }
+
+    fn item(&self, priority: u8) -> Option<char> {
+        match priority {
+            1..=26 => Some((b'a' + priority - 1) as char),
+            27..=52 => Some((b'A' + priority - 27) as char),
+            _ => None,
+        }
+    }
+}
+
+/// Problems with a single line of a priority mapping
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MappingError {
+    /// Lines must hold an item and a priority
+    FieldCount(usize),
+    /// Items must be a single character
+    BadItem(String),
+    /// Priorities must be between 1 and `MAX_PRIORITY`
+    BadPriority(String),
+    DuplicateItem(char),
+    DuplicatePriority(u8),
+}
+
+impl Display for MappingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            MappingError::FieldCount(count) => {
+                write!(f, "expected an item and a priority, found {count} field(s)")
+            }
+            MappingError::BadItem(item) => write!(f, "item '{item}' isn't a single character"),
+            MappingError::BadPriority(priority) => write!(
+                f,
+                "priority '{priority}' isn't a number from 1 to {MAX_PRIORITY}"
+            ),
+            MappingError::DuplicateItem(item) => write!(f, "item '{item}' is mapped twice"),
+            MappingError::DuplicatePriority(priority) => {
+                write!(f, "priority {priority} is used twice")
+            }
+        }
+    }
+}
+
+impl Error for MappingError {}
+
+/// Problems loading a `MappedScheme`
+#[derive(Debug)]
+pub enum SchemeError {
+    Io(io::Error),
+    /// A bad mapping on the given 1-based line
+    Mapping {
+        line: usize,
+        error: MappingError,
+    },
+}
+
+impl Display for SchemeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            SchemeError::Io(err) => write!(f, "failed to read priority mapping: {err}"),
+            SchemeError::Mapping { line, error } => write!(f, "line {line}: {error}"),
+        }
+    }
+}
+
+impl Error for SchemeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SchemeError::Io(err) => Some(err),
+            SchemeError::Mapping { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for SchemeError {
+    fn from(err: io::Error) -> Self {
+        SchemeError::Io(err)
+    }
+}
+
+/// A scheme built from an explicit item to priority mapping, so alphabets can include digits
+/// and symbols
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct MappedScheme {
+    priorities: HashMap<char, u8>,
+    items: HashMap<u8, char>,
+}
+
+impl MappedScheme {
+    /// Parses a mapping with one `<item> <priority>` pair per line. Blank lines are skipped.
+    pub fn from_mapping(mapping: &str) -> Result<Self, SchemeError> {
+        let mut scheme = MappedScheme::default();
+
+        for (idx, line) in mapping.lines().enumerate() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.is_empty() {
+                continue;
+            }
+
+            scheme
+                .add_line(&fields)
+                .map_err(|error| SchemeError::Mapping {
+                    line: idx + 1,
+                    error,
+                })?;
+        }
+
+        Ok(scheme)
+    }
+
+    /// Reads a mapping file in the format taken by `from_mapping`
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, SchemeError> {
+        MappedScheme::from_mapping(&fs::read_to_string(path)?)
+    }
+
+    fn add_line(&mut self, fields: &[&str]) -> Result<(), MappingError> {
+        let (item, priority) = match fields {
+            [item, priority] => (*item, *priority),
+            _ => return Err(MappingError::FieldCount(fields.len())),
+        };
+
+        let mut chars = item.chars();
+        let item = match (chars.next(), chars.next()) {
+            (Some(c), None) => c,
+            _ => return Err(MappingError::BadItem(item.to_string())),
+        };
+        let priority = match priority.parse::<u8>() {
+            Ok(p @ 1..=MAX_PRIORITY) => p,
+            _ => return Err(MappingError::BadPriority(priority.to_string())),
+        };
+
+        if self.priorities.contains_key(&item) {
+            return Err(MappingError::DuplicateItem(item));
+        }
+        if self.items.contains_key(&priority) {
+            return Err(MappingError::DuplicatePriority(priority));
+        }
+
+        self.priorities.insert(item, priority);
+        self.items.insert(priority, item);
+
+        Ok(())
+    }
+
+    /// Number of items in the alphabet
+    pub fn len(&self) -> usize {
+        self.priorities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.priorities.is_empty()
+    }
+}
+
+impl PriorityScheme for MappedScheme {
+    fn priority(&self, item: char) -> Option<u8> {
+        self.priorities.get(&item).copied()
+    }
+
+    fn item(&self, priority: u8) -> Option<char> {
+        self.items.get(&priority).copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn default_priorities() {
+        let scheme = DefaultScheme;
+
+        assert_eq!(Some(1), scheme.priority('a'));
+        assert_eq!(Some(27), scheme.priority('A'));
+        assert_eq!(None, scheme.priority('1'));
+        assert_eq!(None, scheme.priority('é'));
+
+        for priority in 1..=52 {
+            let c = scheme.item(priority).unwrap();
+            assert_eq!(Some(priority), scheme.priority(c));
+        }
+
+        assert_eq!(None, scheme.item(0));
+        assert_eq!(None, scheme.item(53));
+    }
+
+    #[test]
+    fn mapped_priorities() {
+        let scheme = MappedScheme::from_mapping("0 1\n9 10\n\n  # 11\né 64\n").unwrap();
+
+        assert_eq!(4, scheme.len());
+        assert_eq!(Some(10), scheme.priority('9'));
+        assert_eq!(Some(11), scheme.priority('#'));
+        assert_eq!(Some('é'), scheme.item(64));
+        assert_eq!(None, scheme.priority('a'));
+        assert_eq!(None, scheme.item(2));
+    }
+
+    #[test]
+    fn bad_mappings() {
+        let cases = [
+            ("a 1\nb", 2, MappingError::FieldCount(1)),
+            ("ab 1", 1, MappingError::BadItem("ab".to_string())),
+            ("a 0", 1, MappingError::BadPriority("0".to_string())),
+            ("a 65", 1, MappingError::BadPriority("65".to_string())),
+            ("a 1\n\na 2", 3, MappingError::DuplicateItem('a')),
+            ("a 1\nb 1", 2, MappingError::DuplicatePriority(1)),
+        ];
+
+        for (mapping, expected_line, expected_error) in cases {
+            match MappedScheme::from_mapping(mapping) {
+                Err(SchemeError::Mapping { line, error }) => {
+                    assert_eq!(expected_line, line, "{mapping:?}");
+                    assert_eq!(expected_error, error, "{mapping:?}");
+                }
+                other => panic!("{mapping:?} gave {other:?}"),
+            }
+        }
+
+        let err = MappedScheme::from_mapping("a 0").unwrap_err();
+        assert_eq!(
+            "priority '0' isn't a number from 1 to 64",
+            err.source().unwrap().to_string()
+        );
+    }
+}
@@ -0,0 +1,380 @@
+use std::{
+    cmp::Reverse,
+    fmt::{Display, Formatter},
+};
+
+use crate::{
+    priority::{checked_priority, PriorityScheme, MAX_PRIORITY},
+    Rucksack,
+};
+
+/// Widest bar drawn in the text histogram
+const BAR_WIDTH: usize = 40;
+
+/// How often a single item type shows up
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ItemStats {
+    pub item: char,
+    pub priority: u8,
+    /// Occurrences in first compartments
+    pub first: usize,
+    /// Occurrences in second compartments
+    pub second: usize,
+    /// Rucksacks holding at least one
+    pub rucksacks: usize,
+    /// Rucksacks where this is the lowest priority item in both compartments
+    pub misplaced: usize,
+    /// Groups where this is the lowest priority item every member carries
+    pub badges: usize,
+}
+
+impl ItemStats {
+    /// Occurrences across both compartments
+    pub fn count(&self) -> usize {
+        self.first + self.second
+    }
+}
+
+/// Badge and priority of a single group
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GroupStats {
+    pub size: usize,
+    pub badge: Option<char>,
+    /// The badge's priority, or 0 without one
+    pub priority: u8,
+}
+
+/// Statistics over a whole inventory
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Stats {
+    pub rucksacks: usize,
+    /// Every item type seen, in priority order
+    pub items: Vec<ItemStats>,
+    /// Every group, in input order
+    pub groups: Vec<GroupStats>,
+    /// Sum of every rucksack's misplaced item priority
+    pub misplaced_total: u64,
+    /// Sum of every group's badge priority
+    pub badge_total: u64,
+}
+
+impl Stats {
+    /// Collects statistics over groups as returned by `Rucksack::groups_from_lines`
+    pub fn from_groups<S: PriorityScheme>(groups: &[Vec<Rucksack<'_, S>>]) -> Self {
+        let mut stats = Stats::default();
+        let mut items: Vec<Option<ItemStats>> = vec![None; MAX_PRIORITY as usize + 1];
+
+        for group in groups {
+            for rucksack in group {
+                stats.rucksacks += 1;
+                stats.misplaced_total += u64::from(rucksack.get_overlap_priority());
+
+                let compartments = [rucksack.compartments.0, rucksack.compartments.1];
+                for (side, contents) in compartments.into_iter().enumerate() {
+                    for item in contents.chars() {
+                        let entry = match item_entry(&mut items, rucksack.scheme(), item) {
+                            Some(entry) => entry,
+                            None => continue,
+                        };
+
+                        if side == 0 {
+                            entry.first += 1;
+                        } else {
+                            entry.second += 1;
+                        }
+                    }
+                }
+
+                for item in rucksack.items().items_in(rucksack.scheme()) {
+                    if let Some(entry) = item_entry(&mut items, rucksack.scheme(), item) {
+                        entry.rucksacks += 1;
+                    }
+                }
+
+                if let Some(item) = rucksack.get_overlap() {
+                    if let Some(entry) = item_entry(&mut items, rucksack.scheme(), item) {
+                        entry.misplaced += 1;
+                    }
+                }
+            }
+
+            let badge = Rucksack::get_group_overlap(group);
+            let priority = Rucksack::get_group_overlap_priority(group);
+            if let (Some(item), Some(first)) = (badge, group.first()) {
+                if let Some(entry) = item_entry(&mut items, first.scheme(), item) {
+                    entry.badges += 1;
+                }
+            }
+
+            stats.badge_total += u64::from(priority);
+            stats.groups.push(GroupStats {
+                size: group.len(),
+                badge,
+                priority,
+            });
+        }
+
+        stats.items = items.into_iter().flatten().collect();
+        stats
+    }
+
+    /// Items that were misplaced at least once, most often first
+    pub fn most_misplaced(&self) -> Vec<&ItemStats> {
+        let mut items: Vec<&ItemStats> = self
+            .items
+            .iter()
+            .filter(|item| item.misplaced > 0)
+            .collect();
+        items.sort_by_key(|item| Reverse(item.misplaced));
+
+        items
+    }
+
+    /// One row per item type, with a header
+    pub fn items_csv(&self) -> String {
+        let mut csv = String::from("item,priority,count,first,second,rucksacks,misplaced,badges\n");
+        for item in &self.items {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{}\n",
+                csv_field(item.item),
+                item.priority,
+                item.count(),
+                item.first,
+                item.second,
+                item.rucksacks,
+                item.misplaced,
+                item.badges
+            ));
+        }
+
+        csv
+    }
+
+    /// One row per group, with a header. Groups are numbered from 1.
+    pub fn groups_csv(&self) -> String {
+        let mut csv = String::from("group,size,badge,priority\n");
+        for (idx, group) in self.groups.iter().enumerate() {
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                idx + 1,
+                group.size,
+                group.badge.map(csv_field).unwrap_or_default(),
+                group.priority
+            ));
+        }
+
+        csv
+    }
+
+    /// The statistics as a single JSON object
+    pub fn to_json(&self) -> String {
+        let items = self
+            .items
+            .iter()
+            .map(|item| {
+                format!(
+                    "{{\"item\":{},\"priority\":{},\"count\":{},\"first\":{},\"second\":{},\"rucksacks\":{},\"misplaced\":{},\"badges\":{}}}",
+                    json_string(item.item),
+                    item.priority,
+                    item.count(),
+                    item.first,
+                    item.second,
+                    item.rucksacks,
+                    item.misplaced,
+                    item.badges
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        let groups = self
+            .groups
+            .iter()
+            .map(|group| {
+                format!(
+                    "{{\"size\":{},\"badge\":{},\"priority\":{}}}",
+                    group.size,
+                    group.badge.map_or("null".to_string(), json_string),
+                    group.priority
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!(
+            "{{\"rucksacks\":{},\"misplaced_total\":{},\"badge_total\":{},\"items\":[{}],\"groups\":[{}]}}",
+            self.rucksacks, self.misplaced_total, self.badge_total, items, groups
+        )
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let widest = self.items.iter().map(ItemStats::count).max().unwrap_or(0);
+
+        writeln!(
+            f,
+            "{:<6}{:>9}{:>7}{:>7}{:>8}{:>11}{:>11}  Histogram",
+            "Item", "Priority", "Count", "First", "Second", "Rucksacks", "Misplaced"
+        )?;
+        for item in &self.items {
+            let bar = (item.count() * BAR_WIDTH).div_ceil(widest.max(1));
+            writeln!(
+                f,
+                "{:<6}{:>9}{:>7}{:>7}{:>8}{:>11}{:>11}  {}",
+                item.item,
+                item.priority,
+                item.count(),
+                item.first,
+                item.second,
+                item.rucksacks,
+                item.misplaced,
+                "#".repeat(bar)
+            )?;
+        }
+        writeln!(f)?;
+
+        let misplaced: Vec<String> = self
+            .most_misplaced()
+            .iter()
+            .take(5)
+            .map(|item| format!("{} ({})", item.item, item.misplaced))
+            .collect();
+        writeln!(f, "Most misplaced: {}", misplaced.join(", "))?;
+        writeln!(f, "Rucksacks: {}", self.rucksacks)?;
+        writeln!(f, "Groups: {}", self.groups.len())?;
+        writeln!(f, "Misplaced priority total: {}", self.misplaced_total)?;
+        write!(f, "Badge priority total: {}", self.badge_total)
+    }
+}
+
+fn item_entry<'i>(
+    items: &'i mut [Option<ItemStats>],
+    scheme: &impl PriorityScheme,
+    item: char,
+) -> Option<&'i mut ItemStats> {
+    let priority = checked_priority(scheme, item)?;
+
+    Some(items[priority as usize].get_or_insert(ItemStats {
+        item,
+        priority,
+        first: 0,
+        second: 0,
+        rucksacks: 0,
+        misplaced: 0,
+        badges: 0,
+    }))
+}
+
+fn csv_field(item: char) -> String {
+    match item {
+        '"' => "\"\"\"\"".to_string(),
+        ',' | '\n' | '\r' => format!("\"{item}\""),
+        _ => item.to_string(),
+    }
+}
+
+fn json_string(item: char) -> String {
+    match item {
+        '"' | '\\' => format!("\"\\{item}\""),
+        c if c.is_control() => format!("\"\\u{:04x}\"", c as u32),
+        _ => format!("\"{item}\""),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{priority::MappedScheme, PartialGroup};
+
+    const LINES: [&str; 6] = [
+        "vJrwpWtwJgWrhcsFMMfFFhFp",
+        "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+        "PmmdzqPrVvPwwTWBwg",
+        "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+        "ttgJtRGJQctTZtZT",
+        "CrZsJsPPZsGzwwsLwLmpwMDw",
+    ];
+
+    fn stats() -> Stats {
+        let groups = Rucksack::groups_from_lines(&LINES, 3, PartialGroup::Error).unwrap();
+        Stats::from_groups(&groups)
+    }
+
+    #[test]
+    fn puzzle_totals() {
+        let stats = stats();
+
+        assert_eq!(6, stats.rucksacks);
+        assert_eq!(157, stats.misplaced_total);
+        assert_eq!(70, stats.badge_total);
+        assert_eq!(
+            vec![Some('r'), Some('Z')],
+            stats.groups.iter().map(|g| g.badge).collect::<Vec<_>>()
+        );
+
+        let total: usize = stats.items.iter().map(ItemStats::count).sum();
+        assert_eq!(LINES.iter().map(|line| line.len()).sum::<usize>(), total);
+    }
+
+    #[test]
+    fn item_counts() {
+        let stats = stats();
+        let item = |c| stats.items.iter().find(|item| item.item == c).unwrap();
+
+        // "vJrwpWtwJgWr" | "hcsFMMfFFhFp" and "CrZsJsPPZsGz" | "wwsLwLmpwMDw"
+        let p = item('p');
+        assert_eq!((1, 2, 2, 1), (p.first, p.second, p.rucksacks, p.misplaced));
+        assert_eq!(16, p.priority);
+
+        let misplaced: Vec<char> = stats.most_misplaced().iter().map(|i| i.item).collect();
+        assert_eq!(6, misplaced.len());
+        assert_eq!(1, item('Z').badges);
+    }
+
+    #[test]
+    fn exports() {
+        let groups =
+            Rucksack::groups_from_lines(&["aa", "bb", "ab"], 2, PartialGroup::Keep).unwrap();
+        let stats = Stats::from_groups(&groups);
+
+        assert_eq!(
+            "item,priority,count,first,second,rucksacks,misplaced,badges\n\
+             a,1,3,2,1,2,1,1\n\
+             b,2,3,1,2,2,1,0\n",
+            stats.items_csv()
+        );
+        assert_eq!(
+            "group,size,badge,priority\n1,2,,0\n2,1,a,1\n",
+            stats.groups_csv()
+        );
+        assert_eq!(
+            "{\"rucksacks\":3,\"misplaced_total\":3,\"badge_total\":1,\"items\":[\
+             {\"item\":\"a\",\"priority\":1,\"count\":3,\"first\":2,\"second\":1,\"rucksacks\":2,\"misplaced\":1,\"badges\":1},\
+             {\"item\":\"b\",\"priority\":2,\"count\":3,\"first\":1,\"second\":2,\"rucksacks\":2,\"misplaced\":1,\"badges\":0}],\
+             \"groups\":[{\"size\":2,\"badge\":null,\"priority\":0},{\"size\":1,\"badge\":\"a\",\"priority\":1}]}",
+            stats.to_json()
+        );
+    }
+
+    #[test]
+    fn escaped_items() {
+        let scheme = MappedScheme::from_mapping(", 1\n\" 2").unwrap();
+        let rucksack = Rucksack::parse_with(",\"\",", &scheme).unwrap();
+        let stats = Stats::from_groups(&[vec![rucksack]]);
+
+        assert!(stats.items_csv().contains("\n\",\",1,"));
+        assert!(stats.items_csv().contains("\n\"\"\"\",2,"));
+        assert!(stats
+            .to_json()
+            .contains("{\"item\":\"\\\"\",\"priority\":2"));
+    }
+
+    #[test]
+    fn text_report() {
+        let text = stats().to_string();
+
+        assert!(text.starts_with("Item"));
+        assert!(text.contains("Most misplaced: "));
+        assert!(text.ends_with("Badge priority total: 70"));
+    }
+}
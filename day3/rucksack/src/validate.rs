@@ -0,0 +1,575 @@
+use std::fmt::{Display, Formatter};
+
+use crate::{
+    priority::{checked_priority, PriorityScheme, MAX_PRIORITY},
+    ContentsError, GroupError, ItemSet, Rucksack,
+};
+
+/// Item counts indexed by priority
+type Counts = [usize; MAX_PRIORITY as usize + 1];
+
+/// Why a single rucksack is invalid
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Problem {
+    Contents(ContentsError),
+    /// No item type is in both compartments
+    NoSharedItem,
+    /// More than one item type is in both compartments
+    SharedItems(Vec<char>),
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Problem::Contents(err) => write!(f, "{err}"),
+            Problem::NoSharedItem => write!(f, "no item is in both compartments"),
+            Problem::SharedItems(items) => write!(
+                f,
+                "{} items are in both compartments: {}",
+                items.len(),
+                join(items)
+            ),
+        }
+    }
+}
+
+/// Exchange one `first` from the first compartment with one `second` from the second
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Swap {
+    pub first: char,
+    pub second: char,
+}
+
+/// The fewest swaps that leave a rucksack with exactly one shared item
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Repair {
+    pub swaps: Vec<Swap>,
+    /// The only shared item once the swaps are made
+    pub shared: char,
+}
+
+impl Repair {
+    /// Makes the swaps in `contents`, swapping the first matching items of each compartment
+    pub fn apply(&self, contents: &str) -> String {
+        let mut items: Vec<char> = contents.chars().collect();
+        let middle = items.len() / 2;
+
+        for swap in &self.swaps {
+            let first = items[..middle].iter().position(|&c| c == swap.first);
+            let second = items[middle..].iter().position(|&c| c == swap.second);
+
+            if let (Some(first), Some(second)) = (first, second) {
+                items.swap(first, middle + second);
+            }
+        }
+
+        items.into_iter().collect()
+    }
+}
+
+impl Display for Repair {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let swaps: Vec<String> = self
+            .swaps
+            .iter()
+            .map(|swap| format!("'{}' <-> '{}'", swap.first, swap.second))
+            .collect();
+
+        write!(
+            f,
+            "swap {} to share only '{}'",
+            swaps.join(", "),
+            self.shared
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RucksackIssue {
+    /// 1-based line number
+    pub line: usize,
+    pub problem: Problem,
+    /// `None` when no number of swaps can fix the rucksack
+    pub repair: Option<Repair>,
+}
+
+impl Display for RucksackIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "line {}: {}", self.line, self.problem)?;
+        if let Some(repair) = &self.repair {
+            write!(f, " ({repair})")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Why a group of rucksacks is invalid
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum GroupProblem {
+    /// The last group only had `found` of the `expected` rucksacks
+    Incomplete { expected: usize, found: usize },
+    /// No item type is carried by every member
+    NoBadge,
+    /// More than one item type is carried by every member
+    Badges(Vec<char>),
+}
+
+impl Display for GroupProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            GroupProblem::Incomplete { expected, found } => {
+                write!(f, "group has {found} rucksack(s), expected {expected}")
+            }
+            GroupProblem::NoBadge => write!(f, "no item is carried by every member"),
+            GroupProblem::Badges(items) => write!(
+                f,
+                "{} items are carried by every member: {}",
+                items.len(),
+                join(items)
+            ),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GroupIssue {
+    /// 1-based line numbers of the group's first and last rucksacks
+    pub lines: (usize, usize),
+    pub problem: GroupProblem,
+}
+
+impl Display for GroupIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "lines {}-{}: {}",
+            self.lines.0, self.lines.1, self.problem
+        )
+    }
+}
+
+/// Everything wrong with an inventory
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Report {
+    /// Number of rucksacks checked
+    pub rucksacks: usize,
+    pub rucksack_issues: Vec<RucksackIssue>,
+    pub group_issues: Vec<GroupIssue>,
+}
+
+impl Report {
+    pub fn is_valid(&self) -> bool {
+        self.rucksack_issues.is_empty() && self.group_issues.is_empty()
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        for issue in &self.rucksack_issues {
+            writeln!(f, "{issue}")?;
+        }
+        for issue in &self.group_issues {
+            writeln!(f, "{issue}")?;
+        }
+
+        write!(
+            f,
+            "{} rucksack(s), {} invalid rucksack(s), {} invalid group(s)",
+            self.rucksacks,
+            self.rucksack_issues.len(),
+            self.group_issues.len()
+        )
+    }
+}
+
+/// Checks rucksack inventories against the puzzle's assumptions
+pub struct Validator<'s, S> {
+    scheme: &'s S,
+    group_size: Option<usize>,
+}
+
+impl<'s, S: PriorityScheme> Validator<'s, S> {
+    /// A validator that only checks individual rucksacks
+    pub fn new(scheme: &'s S) -> Self {
+        Validator {
+            scheme,
+            group_size: None,
+        }
+    }
+
+    /// Also check that consecutive groups of `group_size` rucksacks share exactly one badge
+    pub fn group_size(mut self, group_size: usize) -> Result<Self, GroupError> {
+        if group_size == 0 {
+            return Err(GroupError::ZeroSize);
+        }
+
+        self.group_size = Some(group_size);
+        Ok(self)
+    }
+
+    pub fn validate(&self, lines: &[&str]) -> Report {
+        let mut report = Report {
+            rucksacks: lines.len(),
+            ..Report::default()
+        };
+
+        let mut rucksacks = Vec::with_capacity(lines.len());
+        for (idx, line) in lines.iter().enumerate() {
+            let rucksack = match Rucksack::parse_with(line, self.scheme) {
+                Ok(rucksack) => rucksack,
+                Err(err) => {
+                    report.rucksack_issues.push(RucksackIssue {
+                        line: idx + 1,
+                        problem: Problem::Contents(err),
+                        repair: None,
+                    });
+                    rucksacks.push(None);
+                    continue;
+                }
+            };
+
+            let shared = rucksack.shared_items();
+            let problem = match shared.len() {
+                0 => Some(Problem::NoSharedItem),
+                1 => None,
+                _ => Some(Problem::SharedItems(self.names(shared))),
+            };
+            if let Some(problem) = problem {
+                report.rucksack_issues.push(RucksackIssue {
+                    line: idx + 1,
+                    problem,
+                    repair: self.repair(&rucksack),
+                });
+            }
+
+            rucksacks.push(Some(rucksack));
+        }
+
+        if let Some(group_size) = self.group_size {
+            for (idx, group) in rucksacks.chunks(group_size).enumerate() {
+                let first_line = idx * group_size + 1;
+                if let Some(problem) = self.check_group(group, group_size) {
+                    report.group_issues.push(GroupIssue {
+                        lines: (first_line, first_line + group.len() - 1),
+                        problem,
+                    });
+                }
+            }
+        }
+
+        report
+    }
+
+    fn names(&self, items: ItemSet) -> Vec<char> {
+        items.items_in(self.scheme).collect()
+    }
+
+    /// Groups with unparseable members are skipped since those members are already reported
+    fn check_group(
+        &self,
+        group: &[Option<Rucksack<'_, S>>],
+        group_size: usize,
+    ) -> Option<GroupProblem> {
+        if group.len() < group_size {
+            return Some(GroupProblem::Incomplete {
+                expected: group_size,
+                found: group.len(),
+            });
+        }
+
+        let members: Option<Vec<ItemSet>> = group
+            .iter()
+            .map(|rucksack| rucksack.as_ref().map(Rucksack::items))
+            .collect();
+        let shared = members?
+            .into_iter()
+            .reduce(|shared, items| shared & items)?;
+
+        match shared.len() {
+            0 => Some(GroupProblem::NoBadge),
+            1 => None,
+            _ => Some(GroupProblem::Badges(self.names(shared))),
+        }
+    }
+
+    fn repair(&self, rucksack: &Rucksack<'_, S>) -> Option<Repair> {
+        let first = self.counts(rucksack.compartments.0);
+        let second = self.counts(rucksack.compartments.1);
+        let (shared, target) = plan_repair(&first, &second, rucksack.len() / 2)?;
+
+        // Items leaving the first compartment are paired with items arriving from the second
+        let mut outgoing = Vec::new();
+        let mut incoming = Vec::new();
+        for priority in 1..=MAX_PRIORITY {
+            let (current, wanted) = (first[priority as usize], target[priority as usize]);
+            let item = self.scheme.item(priority);
+
+            if current > wanted {
+                outgoing.extend(item.into_iter().cycle().take(current - wanted));
+            } else {
+                incoming.extend(item.into_iter().cycle().take(wanted - current));
+            }
+        }
+
+        Some(Repair {
+            swaps: outgoing
+                .into_iter()
+                .zip(incoming)
+                .map(|(first, second)| Swap { first, second })
+                .collect(),
+            shared: self.scheme.item(shared)?,
+        })
+    }
+
+    fn counts(&self, items: &str) -> Counts {
+        let mut counts = [0; MAX_PRIORITY as usize + 1];
+        for item in items.chars() {
+            if let Some(priority) = checked_priority(&self.scheme, item) {
+                counts[priority as usize] += 1;
+            }
+        }
+
+        counts
+    }
+}
+
+/// Finds how many of each item the first compartment should hold so that exactly one item is
+/// in both, moving as few items as possible. Returns the shared item's priority and the counts.
+///
+/// Every item other than the shared one has to sit wholly in one compartment, so this is a
+/// knapsack over which items fill the first compartment, tried once per possible shared item.
+fn plan_repair(first: &Counts, second: &Counts, half: usize) -> Option<(u8, Counts)> {
+    let types: Vec<usize> = (1..first.len())
+        .filter(|&t| first[t] + second[t] > 0)
+        .collect();
+    let mut best: Option<(usize, u8, Counts)> = None;
+
+    for &shared in &types {
+        let total = first[shared] + second[shared];
+        if total < 2 {
+            continue;
+        }
+
+        // costs[i][size] is the fewest items moved out of the first compartment when the first
+        // `i` other types fill `size` slots of it
+        let others: Vec<usize> = types.iter().copied().filter(|&t| t != shared).collect();
+        let mut costs = vec![vec![None; half + 1]];
+        costs[0][0] = Some(0);
+
+        for &t in &others {
+            let prev = &costs[costs.len() - 1];
+            let total = first[t] + second[t];
+            let mut next: Vec<Option<usize>> = vec![None; half + 1];
+
+            for size in 0..=half {
+                let leave = prev[size].map(|cost| cost + first[t]);
+                let take = size.checked_sub(total).and_then(|rest| prev[rest]);
+                next[size] = match (leave, take) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+            }
+
+            costs.push(next);
+        }
+
+        for kept in 1..total.min(half + 1) {
+            let cost = match costs[others.len()][half - kept] {
+                Some(cost) => cost + first[shared].saturating_sub(kept),
+                None => continue,
+            };
+            if best
+                .as_ref()
+                .is_some_and(|(best_cost, _, _)| *best_cost <= cost)
+            {
+                continue;
+            }
+
+            let mut target = [0; MAX_PRIORITY as usize + 1];
+            target[shared] = kept;
+
+            let mut size = half - kept;
+            for (i, &t) in others.iter().enumerate().rev() {
+                let total = first[t] + second[t];
+                let leave = costs[i][size].map(|prev| prev + first[t]);
+                if leave != costs[i + 1][size] {
+                    target[t] = total;
+                    size -= total;
+                }
+            }
+
+            best = Some((cost, shared as u8, target));
+        }
+    }
+
+    best.map(|(_, shared, target)| (shared, target))
+}
+
+fn join(items: &[char]) -> String {
+    items
+        .iter()
+        .map(char::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::priority::DefaultScheme;
+
+    fn repaired(line: &str) -> (Repair, String) {
+        let report = Validator::new(&DefaultScheme).validate(&[line]);
+        let repair = report.rucksack_issues[0].repair.clone().unwrap();
+        let fixed = repair.apply(line);
+
+        (repair, fixed)
+    }
+
+    #[test]
+    fn puzzle_input_is_valid() {
+        let lines = [
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "PmmdzqPrVvPwwTWBwg",
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "ttgJtRGJQctTZtZT",
+            "CrZsJsPPZsGzwwsLwLmpwMDw",
+        ];
+        let report = Validator::new(&DefaultScheme)
+            .group_size(3)
+            .unwrap()
+            .validate(&lines);
+
+        assert!(report.is_valid());
+        assert_eq!(6, report.rucksacks);
+    }
+
+    #[test]
+    fn rucksack_problems() {
+        let lines = ["abcd", "abab", "abc", "ab1d", "aabb"];
+        let report = Validator::new(&DefaultScheme).validate(&lines);
+
+        let problems: Vec<(usize, Problem)> = report
+            .rucksack_issues
+            .iter()
+            .map(|issue| (issue.line, issue.problem.clone()))
+            .collect();
+        assert_eq!(
+            vec![
+                (1, Problem::NoSharedItem),
+                (2, Problem::SharedItems(vec!['a', 'b'])),
+                (3, Problem::Contents(ContentsError::OddLength(3))),
+                (
+                    4,
+                    Problem::Contents(ContentsError::UnknownItem {
+                        item: '1',
+                        column: 3
+                    })
+                ),
+                (5, Problem::NoSharedItem),
+            ],
+            problems
+        );
+        assert!(!report.is_valid());
+    }
+
+    #[test]
+    fn minimal_repairs() {
+        // Moving a 'c' out for a 'b' leaves 'a' as the only shared item
+        let (repair, fixed) = repaired("abcabc");
+        assert_eq!(1, repair.swaps.len());
+        assert_eq!(1, Rucksack::from_string(&fixed).shared_items().len());
+
+        let (repair, fixed) = repaired("aabc");
+        assert_eq!(
+            vec![Swap {
+                first: 'a',
+                second: 'b'
+            }],
+            repair.swaps
+        );
+        assert_eq!("baac", fixed);
+        assert_eq!(
+            Some(repair.shared),
+            Rucksack::from_string(&fixed).get_overlap()
+        );
+
+        // Every item but the shared one has to end up wholly on one side
+        let (repair, fixed) = repaired("abcdeabcde");
+        assert_eq!(2, repair.swaps.len());
+        assert_eq!(1, Rucksack::from_string(&fixed).shared_items().len());
+    }
+
+    #[test]
+    fn unrepairable() {
+        // Every item type appears once, so none can be in both compartments
+        let report = Validator::new(&DefaultScheme).validate(&["abcd"]);
+        assert_eq!(None, report.rucksack_issues[0].repair);
+
+        // Sharing one 'a' leaves a single slot for the pair of 'b's
+        let report = Validator::new(&DefaultScheme).validate(&["abab", "aabb"]);
+        assert_eq!(None, report.rucksack_issues[0].repair);
+        assert_eq!(None, report.rucksack_issues[1].repair);
+    }
+
+    #[test]
+    fn group_problems() {
+        let lines = ["aa", "aa", "bb", "cc", "dd"];
+        let report = Validator::new(&DefaultScheme)
+            .group_size(2)
+            .unwrap()
+            .validate(&lines);
+
+        assert!(report.rucksack_issues.is_empty());
+        assert_eq!(
+            vec![
+                GroupIssue {
+                    lines: (3, 4),
+                    problem: GroupProblem::NoBadge
+                },
+                GroupIssue {
+                    lines: (5, 5),
+                    problem: GroupProblem::Incomplete {
+                        expected: 2,
+                        found: 1
+                    }
+                },
+            ],
+            report.group_issues
+        );
+
+        let report = Validator::new(&DefaultScheme)
+            .group_size(2)
+            .unwrap()
+            .validate(&["abab", "abab"]);
+        assert_eq!(
+            GroupProblem::Badges(vec!['a', 'b']),
+            report.group_issues[0].problem
+        );
+    }
+
+    #[test]
+    fn zero_group_size() {
+        assert!(matches!(
+            Validator::new(&DefaultScheme).group_size(0),
+            Err(GroupError::ZeroSize)
+        ));
+    }
+
+    #[test]
+    fn report_text() {
+        let report = Validator::new(&DefaultScheme)
+            .group_size(2)
+            .unwrap()
+            .validate(&["aabc", "abc"]);
+
+        assert_eq!(
+            "line 1: no item is in both compartments (swap 'a' <-> 'b' to share only 'a')\n\
+             line 2: 3 item(s) can't be split into two compartments\n\
+             2 rucksack(s), 2 invalid rucksack(s), 0 invalid group(s)",
+            report.to_string()
+        );
+    }
+}
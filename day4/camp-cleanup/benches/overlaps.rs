@@ -0,0 +1,95 @@
+use camp_cleanup::{IntervalIndex, SectionAssignment};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// Builds `count` ranges of up to 100 sections, spread over ten sections per range
+fn assignments(count: usize) -> Vec<SectionAssignment> {
+    let mut rng = fastrand::Rng::with_seed(0x2022_0004);
+
+    (0..count)
+        .map(|_| {
+            let start = rng.usize(..count * 10);
+            SectionAssignment::new(start, start + rng.usize(..100)).unwrap()
+        })
+        .collect()
+}
+
+fn naive_pair_count(assignments: &[SectionAssignment]) -> usize {
+    let mut count = 0;
+    for (i, a) in assignments.iter().enumerate() {
+        count += assignments[i + 1..]
+            .iter()
+            .filter(|b| a.overlaps(b))
+            .count();
+    }
+
+    count
+}
+
+fn build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("build");
+    group.sample_size(10);
+
+    for count in [100_000, 1_000_000] {
+        let ranges = assignments(count);
+        group.bench_with_input(BenchmarkId::from_parameter(count), &ranges, |b, ranges| {
+            b.iter(|| IntervalIndex::new(black_box(ranges).iter().copied()))
+        });
+    }
+
+    group.finish();
+}
+
+fn queries(c: &mut Criterion) {
+    let mut group = c.benchmark_group("queries");
+
+    for count in [100_000, 1_000_000, 5_000_000] {
+        let ranges = assignments(count);
+        let index = IntervalIndex::new(ranges.iter().copied());
+        let query = SectionAssignment::new(count * 5, count * 5 + 50).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("section", count), &index, |b, index| {
+            b.iter(|| index.containing(black_box(count * 5)))
+        });
+        group.bench_with_input(BenchmarkId::new("range", count), &index, |b, index| {
+            b.iter(|| index.overlapping(black_box(&query)))
+        });
+        group.bench_with_input(BenchmarkId::new("scan", count), &ranges, |b, ranges| {
+            b.iter(|| {
+                black_box(ranges)
+                    .iter()
+                    .filter(|range| range.overlaps(&query))
+                    .count()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn pairs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pairs");
+    group.sample_size(10);
+
+    for count in [10_000, 1_000_000] {
+        let ranges = assignments(count);
+        let index = IntervalIndex::new(ranges.iter().copied());
+
+        group.bench_with_input(BenchmarkId::new("count", count), &index, |b, index| {
+            b.iter(|| index.overlapping_pair_count())
+        });
+        group.bench_with_input(BenchmarkId::new("list", count), &index, |b, index| {
+            b.iter(|| index.overlapping_pairs().len())
+        });
+    }
+
+    // The quadratic scan is only practical on the small input
+    let ranges = assignments(10_000);
+    group.bench_with_input(BenchmarkId::new("naive", 10_000), &ranges, |b, ranges| {
+        b.iter(|| naive_pair_count(black_box(ranges)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, build, queries, pairs);
+criterion_main!(benches);
@@ -0,0 +1,165 @@
+use std::str::FromStr;
+
+use crate::{
+    coverage::sweep, Closed, ParseAssignmentError, SectionAssignment, SectionId, Semantics,
+};
+
+/// The assignments of every elf in a crew, in input order
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct AssignmentSet<T = usize, S = Closed> {
+    ranges: Vec<SectionAssignment<T, S>>,
+}
+
+impl<T: SectionId, S: Semantics> FromStr for AssignmentSet<T, S> {
+    type Err = ParseAssignmentError;
+
+    /// Parses any number of comma-separated ranges
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let ranges = input.split(',').map(str::parse).collect::<Result<_, _>>()?;
+
+        Ok(AssignmentSet { ranges })
+    }
+}
+
+impl<T, S> From<Vec<SectionAssignment<T, S>>> for AssignmentSet<T, S> {
+    fn from(ranges: Vec<SectionAssignment<T, S>>) -> Self {
+        AssignmentSet { ranges }
+    }
+}
+
+impl<T: SectionId, S: Semantics> AssignmentSet<T, S> {
+    pub fn ranges(&self) -> &[SectionAssignment<T, S>] {
+        &self.ranges
+    }
+
+    /// Number of elves in the crew
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Index of a range that contains every other range, if there is one
+    pub fn containing_all(&self) -> Option<usize> {
+        let hull = self.ranges.iter().copied().reduce(|a, b| a.hull(&b))?;
+        self.ranges.iter().position(|range| *range == hull)
+    }
+
+    /// Index pairs of every two ranges that share a section, lowest indices first
+    pub fn overlapping_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for (i, a) in self.ranges.iter().enumerate() {
+            for (j, b) in self.ranges.iter().enumerate().skip(i + 1) {
+                if a.overlaps(b) {
+                    pairs.push((i, j));
+                }
+            }
+        }
+
+        pairs
+    }
+
+    /// The sections assigned to every elf
+    pub fn common_intersection(&self) -> Option<SectionAssignment<T, S>> {
+        let (first, rest) = self.ranges.split_first()?;
+        rest.iter()
+            .try_fold(*first, |common, range| common.intersection(range))
+    }
+
+    /// The sections assigned to exactly `count` elves, as sorted, non-adjacent ranges.
+    ///
+    /// A `count` of 0 gives the gaps between the ranges.
+    pub fn covered_exactly(&self, count: usize) -> Vec<SectionAssignment<T, S>> {
+        sweep(&self.ranges)
+            .into_iter()
+            .filter(|segment| segment.count == count)
+            .map(|segment| segment.range)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn set(input: &str) -> AssignmentSet {
+        input.parse().unwrap()
+    }
+
+    fn ranges(input: &str) -> Vec<SectionAssignment> {
+        set(input).ranges
+    }
+
+    #[test]
+    fn parse_any_number_of_ranges() {
+        assert_eq!(1, set("2-4").len());
+        assert_eq!(4, set("2-4,6-8,1-1,3-9").len());
+        assert!("2-4,,6-8".parse::<AssignmentSet>().is_err());
+        assert!("2-4,8-6".parse::<AssignmentSet>().is_err());
+    }
+
+    #[test]
+    fn containing_all() {
+        assert_eq!(Some(1), set("4-8,1-9,2-3").containing_all());
+        assert_eq!(None, set("4-8,1-7,2-3").containing_all());
+        assert_eq!(Some(0), set("5-5").containing_all());
+        assert_eq!(None, AssignmentSet::<usize>::default().containing_all());
+    }
+
+    #[test]
+    fn overlapping_pairs() {
+        assert_eq!(
+            vec![(0, 1), (0, 3), (1, 3)],
+            set("2-6,5-7,9-9,1-5").overlapping_pairs()
+        );
+        assert!(set("1-2,3-4").overlapping_pairs().is_empty());
+    }
+
+    #[test]
+    fn common_intersection() {
+        assert_eq!(
+            SectionAssignment::new(5, 6),
+            set("2-6,5-9,1-7").common_intersection()
+        );
+        assert_eq!(None, set("2-6,5-9,1-4").common_intersection());
+        assert_eq!(
+            None,
+            AssignmentSet::<usize>::default().common_intersection()
+        );
+    }
+
+    #[test]
+    fn covered_exactly() {
+        let crew = set("1-5,3-8,4-4,10-12");
+
+        assert_eq!(ranges("1-2,6-8,10-12"), crew.covered_exactly(1));
+        assert_eq!(ranges("3-3,5-5"), crew.covered_exactly(2));
+        assert_eq!(ranges("4-4"), crew.covered_exactly(3));
+        assert_eq!(ranges("9-9"), crew.covered_exactly(0));
+        assert!(crew.covered_exactly(4).is_empty());
+
+        // Touching ranges with the same coverage merge into one
+        assert_eq!(ranges("1-8"), set("1-4,5-8").covered_exactly(1));
+        assert_eq!(ranges("3-6"), set("1-4,3-6,5-8").covered_exactly(2));
+    }
+
+    #[test]
+    fn covered_up_to_max() {
+        let top = SectionAssignment::new(usize::MAX - 2, usize::MAX).unwrap();
+        let crew = AssignmentSet::from(vec![
+            top,
+            SectionAssignment::new(usize::MAX, usize::MAX).unwrap(),
+        ]);
+
+        assert_eq!(
+            vec![SectionAssignment::new(usize::MAX - 2, usize::MAX - 1).unwrap()],
+            crew.covered_exactly(1)
+        );
+        assert_eq!(
+            vec![SectionAssignment::new(usize::MAX, usize::MAX).unwrap()],
+            crew.covered_exactly(2)
+        );
+    }
+}
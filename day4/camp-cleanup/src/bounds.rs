@@ -0,0 +1,107 @@
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    num::ParseIntError,
+};
+
+/// An integer type that can number sections
+pub trait SectionId: Copy + Ord + Hash + Debug + Display {
+    /// Whether IDs can be negative, so a leading '-' is a sign rather than a separator
+    const SIGNED: bool;
+
+    fn parse_id(input: &str) -> Result<Self, ParseIntError>;
+
+    /// The following ID, or `None` past the largest one
+    fn next(self) -> Option<Self>;
+
+    /// The preceding ID, or `None` before the smallest one
+    fn prev(self) -> Option<Self>;
+
+    /// How many IDs `from` is below this one. `from` must not be above it.
+    fn offset_from(self, from: Self) -> u128;
+
+    /// The ID `steps` above this one, or `None` past the largest one
+    fn forward(self, steps: u128) -> Option<Self>;
+}
+
+macro_rules! section_id {
+    ($signed:literal, $add:ident: $($int:ty => $unsigned:ty),*) => {
+        $(impl SectionId for $int {
+            const SIGNED: bool = $signed;
+
+            fn parse_id(input: &str) -> Result<Self, ParseIntError> {
+                input.parse()
+            }
+
+            fn next(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn prev(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn offset_from(self, from: Self) -> u128 {
+                // The distance always fits in the unsigned type of the same width
+                self.wrapping_sub(from) as $unsigned as u128
+            }
+
+            fn forward(self, steps: u128) -> Option<Self> {
+                <$unsigned>::try_from(steps)
+                    .ok()
+                    .and_then(|steps| self.$add(steps))
+            }
+        })*
+    };
+}
+
+section_id!(false, checked_add:
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize);
+section_id!(true, checked_add_unsigned:
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+/// Whether a range's end is one of its sections or the first one past it
+pub trait Semantics: Copy + Ord + Hash + Debug + Default {
+    /// Written between the bounds, e.g. `2-4` or `2..5`
+    const SEPARATOR: &'static str;
+
+    /// The final section of the range from `start` to `end`, or `None` if it holds none
+    fn last<T: SectionId>(start: T, end: T) -> Option<T>;
+
+    /// The end to store for a range whose final section is `last`, if it can be written
+    fn end_for<T: SectionId>(last: T) -> Option<T>;
+}
+
+/// Ranges like `2-4`, whose end is their final section
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct Closed;
+
+/// Ranges like `2..5`, whose end is just past their final section.
+///
+/// These can't reach the largest ID, but every one of them has an equivalent closed range.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct HalfOpen;
+
+impl Semantics for Closed {
+    const SEPARATOR: &'static str = "-";
+
+    fn last<T: SectionId>(start: T, end: T) -> Option<T> {
+        (start <= end).then_some(end)
+    }
+
+    fn end_for<T: SectionId>(last: T) -> Option<T> {
+        Some(last)
+    }
+}
+
+impl Semantics for HalfOpen {
+    const SEPARATOR: &'static str = "..";
+
+    fn last<T: SectionId>(start: T, end: T) -> Option<T> {
+        end.prev().filter(|&last| start <= last)
+    }
+
+    fn end_for<T: SectionId>(last: T) -> Option<T> {
+        last.next()
+    }
+}
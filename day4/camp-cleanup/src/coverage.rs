@@ -0,0 +1,313 @@
+use crate::{Closed, SectionAssignment, SectionId, Semantics};
+
+/// A run of sections that are all cleaned by the same number of elves
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Segment<T = usize, S = Closed> {
+    pub range: SectionAssignment<T, S>,
+    pub count: usize,
+}
+
+/// Splits the span of `ranges` into segments of constant coverage, in order.
+///
+/// Consecutive segments always have different counts, and gaps show up with a count of 0.
+pub(crate) fn sweep<T: SectionId, S: Semantics>(
+    ranges: &[SectionAssignment<T, S>],
+) -> Vec<Segment<T, S>> {
+    // Coverage changes at each range's start and just past each range's last section
+    let mut events: Vec<(T, isize)> = Vec::with_capacity(ranges.len() * 2);
+    for range in ranges {
+        events.push((range.start(), 1));
+        if let Some(past_last) = range.last().next() {
+            events.push((past_last, -1));
+        }
+    }
+    events.sort_unstable();
+
+    let mut segments: Vec<Segment<T, S>> = Vec::new();
+    let mut coverage: isize = 0;
+    let mut idx = 0;
+
+    while idx < events.len() {
+        let position = events[idx].0;
+        while idx < events.len() && events[idx].0 == position {
+            coverage += events[idx].1;
+            idx += 1;
+        }
+
+        let last = match events.get(idx) {
+            Some(&(next, _)) => next.prev().expect("later events are above `position`"),
+            // Only ranges ending at the largest ID leave coverage open past the last event
+            None if coverage > 0 => ranges.iter().map(|range| range.last()).max().unwrap(),
+            None => break,
+        };
+        let count = coverage as usize;
+        let range = SectionAssignment::from_last(position, last)
+            .expect("segments lie within the ranges' hull");
+
+        match segments.last_mut() {
+            Some(last) if last.count == count => last.range = last.range.hull(&range),
+            _ => segments.push(Segment { range, count }),
+        }
+    }
+
+    segments
+}
+
+/// How every section in the camp is covered by a set of assignments
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct CoverageMap<T = usize, S = Closed> {
+    assignments: Vec<SectionAssignment<T, S>>,
+    segments: Vec<Segment<T, S>>,
+}
+
+impl<T: SectionId, S: Semantics> CoverageMap<T, S> {
+    pub fn new(assignments: impl IntoIterator<Item = SectionAssignment<T, S>>) -> Self {
+        let assignments: Vec<SectionAssignment<T, S>> = assignments.into_iter().collect();
+        let segments = sweep(&assignments);
+
+        CoverageMap {
+            assignments,
+            segments,
+        }
+    }
+
+    /// The assignments, in the order they were given
+    pub fn assignments(&self) -> &[SectionAssignment<T, S>] {
+        &self.assignments
+    }
+
+    /// Every segment from the lowest to the highest assigned section, including gaps
+    pub fn segments(&self) -> &[Segment<T, S>] {
+        &self.segments
+    }
+
+    /// Number of elves cleaning `section`
+    pub fn coverage_at(&self, section: T) -> usize {
+        let idx = self
+            .segments
+            .partition_point(|segment| segment.range.last() < section);
+
+        match self.segments.get(idx) {
+            Some(segment) if segment.range.contains(section) => segment.count,
+            _ => 0,
+        }
+    }
+
+    /// The sections cleaned by at least one elf, as sorted, non-adjacent ranges
+    pub fn covered(&self) -> Vec<SectionAssignment<T, S>> {
+        let mut covered: Vec<SectionAssignment<T, S>> = Vec::new();
+        for segment in self.segments.iter().filter(|segment| segment.count > 0) {
+            match covered.last_mut() {
+                Some(last) if last.is_adjacent(&segment.range) => *last = last.hull(&segment.range),
+                _ => covered.push(segment.range),
+            }
+        }
+
+        covered
+    }
+
+    /// Sections between the lowest and highest assigned ones that nobody cleans
+    pub fn gaps(&self) -> Vec<SectionAssignment<T, S>> {
+        self.segments
+            .iter()
+            .filter(|segment| segment.count == 0)
+            .map(|segment| segment.range)
+            .collect()
+    }
+
+    /// Segments cleaned by more than one elf
+    pub fn redundant(&self) -> Vec<Segment<T, S>> {
+        self.segments
+            .iter()
+            .filter(|segment| segment.count > 1)
+            .copied()
+            .collect()
+    }
+
+    /// Number of sections cleaned by more than one elf, saturating like `SectionAssignment::len`
+    pub fn redundant_sections(&self) -> u128 {
+        self.redundant().iter().fold(0, |total, segment| {
+            total.saturating_add(segment.range.len())
+        })
+    }
+
+    /// Number of sections cleaned by at least one elf, saturating like `SectionAssignment::len`
+    pub fn covered_sections(&self) -> u128 {
+        self.covered()
+            .iter()
+            .fold(0, |total, range| total.saturating_add(range.len()))
+    }
+
+    /// Indices of the fewest assignments that still cover every covered section, sorted.
+    ///
+    /// Each covered run is filled greedily: of the assignments starting inside what's covered
+    /// so far, take the one reaching furthest.
+    pub fn minimum_cover(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.assignments.len()).collect();
+        order.sort_by_key(|&idx| self.assignments[idx]);
+
+        let mut chosen = Vec::new();
+        let mut next = 0;
+
+        for run in self.covered() {
+            let mut uncovered = Some(run.start());
+
+            while let Some(from) = uncovered.filter(|&from| from <= run.last()) {
+                let mut best: Option<usize> = None;
+                while next < order.len() && self.assignments[order[next]].start() <= from {
+                    let candidate = order[next];
+                    if best.map_or(true, |best| {
+                        self.assignments[candidate].last() > self.assignments[best].last()
+                    }) {
+                        best = Some(candidate);
+                    }
+                    next += 1;
+                }
+
+                // The run is covered, so something always starts at or before `from`
+                let best = best.expect("covered runs can always be extended");
+                chosen.push(best);
+                uncovered = self.assignments[best].last().next();
+            }
+        }
+
+        chosen.sort_unstable();
+        chosen
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{AssignmentSet, HalfOpen};
+
+    fn ranges(input: &str) -> Vec<SectionAssignment> {
+        input.parse::<AssignmentSet>().unwrap().ranges().to_vec()
+    }
+
+    fn segments(input: &[(&str, usize)]) -> Vec<Segment> {
+        input
+            .iter()
+            .map(|&(range, count)| Segment {
+                range: range.parse().unwrap(),
+                count,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sweep_segments() {
+        let map = CoverageMap::new(ranges("3-8,1-5,10-12,4-4"));
+
+        assert_eq!(
+            segments(&[
+                ("1-2", 1),
+                ("3-3", 2),
+                ("4-4", 3),
+                ("5-5", 2),
+                ("6-8", 1),
+                ("9-9", 0),
+                ("10-12", 1)
+            ]),
+            map.segments()
+        );
+        assert_eq!(3, map.coverage_at(4));
+        assert_eq!(0, map.coverage_at(9));
+        assert_eq!(0, map.coverage_at(0));
+        assert_eq!(0, map.coverage_at(13));
+        assert!(CoverageMap::<usize>::default().segments().is_empty());
+    }
+
+    #[test]
+    fn gaps_and_redundancy() {
+        let map = CoverageMap::new(ranges("3-8,1-5,10-12,4-4,13-14"));
+
+        assert_eq!(ranges("1-8,10-14"), map.covered());
+        assert_eq!(ranges("9-9"), map.gaps());
+        assert_eq!(
+            segments(&[("3-3", 2), ("4-4", 3), ("5-5", 2)]),
+            map.redundant()
+        );
+        assert_eq!(3, map.redundant_sections());
+        assert_eq!(13, map.covered_sections());
+    }
+
+    #[test]
+    fn sections_up_to_max() {
+        let all = SectionAssignment::new(0, usize::MAX).unwrap();
+        let map = CoverageMap::new([all, all, SectionAssignment::new(5, 9).unwrap()]);
+
+        assert_eq!(1 << usize::BITS, map.covered_sections());
+        assert_eq!(1 << usize::BITS, map.redundant_sections());
+    }
+
+    #[test]
+    fn any_integer() {
+        let crew: AssignmentSet<i64, HalfOpen> = "-10..-4,-6..0,5..8".parse().unwrap();
+        let map = CoverageMap::new(crew.ranges().iter().copied());
+
+        assert_eq!(2, map.coverage_at(-5));
+        assert_eq!(0, map.coverage_at(2));
+        assert_eq!(
+            vec![SectionAssignment::half_open(0, 5).unwrap()],
+            map.gaps()
+        );
+        assert_eq!(2, map.redundant_sections());
+        assert_eq!(13, map.covered_sections());
+        assert_eq!(vec![0, 1, 2], map.minimum_cover());
+
+        let all = SectionAssignment::new(i128::MIN, i128::MAX).unwrap();
+        let map = CoverageMap::new([all, SectionAssignment::new(-1, 1).unwrap()]);
+        assert_eq!(u128::MAX, map.covered_sections());
+        assert_eq!(3, map.redundant_sections());
+    }
+
+    #[test]
+    fn minimum_cover() {
+        let map = CoverageMap::new(ranges("1-4,2-6,5-9,3-7,8-9,12-15,12-13,14-15"));
+        let cover = map.minimum_cover();
+
+        assert_eq!(vec![0, 2, 5], cover);
+        let kept = CoverageMap::new(cover.iter().map(|&idx| map.assignments()[idx]));
+        assert_eq!(map.covered(), kept.covered());
+
+        // Adjacent runs need both halves
+        let map = CoverageMap::new(ranges("1-3,4-6,1-2"));
+        assert_eq!(vec![0, 1], map.minimum_cover());
+        assert!(CoverageMap::<usize>::default().minimum_cover().is_empty());
+    }
+
+    #[test]
+    fn minimum_cover_is_minimal() {
+        // Compare against every subset for small generated inputs
+        let mut rng = fastrand::Rng::with_seed(4);
+
+        for _ in 0..200 {
+            let count = 1 + rng.usize(..8);
+            let assignments: Vec<SectionAssignment> = (0..count)
+                .map(|_| {
+                    let start = rng.usize(..20);
+                    SectionAssignment::new(start, start + rng.usize(..6)).unwrap()
+                })
+                .collect();
+            let map = CoverageMap::new(assignments.clone());
+            let covered = map.covered();
+
+            let smallest = (1u32..1 << count)
+                .filter(|mask| {
+                    let subset = (0..count)
+                        .filter(|idx| mask & (1 << idx) != 0)
+                        .map(|idx| assignments[idx]);
+                    CoverageMap::new(subset).covered() == covered
+                })
+                .map(u32::count_ones)
+                .min()
+                .unwrap();
+
+            let cover = map.minimum_cover();
+            assert_eq!(smallest as usize, cover.len(), "{assignments:?}");
+            let kept = CoverageMap::new(cover.iter().map(|&idx| assignments[idx]));
+            assert_eq!(covered, kept.covered());
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use crate::{Closed, SectionAssignment, SectionId, Semantics};
+
+/// A static interval tree over many assignments.
+///
+/// The assignments are sorted by start and the tree is implicit: each subtree is a slice of
+/// that order, rooted at its middle element, and remembers the furthest section inside it.
+/// Results are reported as indices into the original assignment order.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct IntervalIndex<T = usize, S = Closed> {
+    ranges: Vec<SectionAssignment<T, S>>,
+    /// Original index of each entry in `ranges`
+    ids: Vec<usize>,
+    /// Furthest last section in the subtree rooted at each entry
+    max_last: Vec<T>,
+}
+
+impl<T: SectionId, S: Semantics> IntervalIndex<T, S> {
+    pub fn new(assignments: impl IntoIterator<Item = SectionAssignment<T, S>>) -> Self {
+        let mut entries: Vec<(SectionAssignment<T, S>, usize)> = assignments
+            .into_iter()
+            .enumerate()
+            .map(|(id, range)| (range, id))
+            .collect();
+        entries.sort_unstable();
+
+        let (ranges, ids): (Vec<_>, Vec<_>) = entries.into_iter().unzip();
+        let mut index = IntervalIndex {
+            max_last: ranges.iter().map(SectionAssignment::last).collect(),
+            ranges,
+            ids,
+        };
+        index.build(0, index.ranges.len());
+
+        index
+    }
+
+    /// Fills in `max_last` for the subtree over `lo..hi`, returning its furthest last section
+    fn build(&mut self, lo: usize, hi: usize) -> Option<T> {
+        if lo >= hi {
+            return None;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        let left = self.build(lo, mid);
+        let right = self.build(mid + 1, hi);
+        self.max_last[mid] = [left, right]
+            .into_iter()
+            .flatten()
+            .fold(self.ranges[mid].last(), T::max);
+
+        Some(self.max_last[mid])
+    }
+
+    /// Number of indexed assignments
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Indices of every assignment that includes `section`, sorted
+    pub fn containing(&self, section: T) -> Vec<usize> {
+        self.sharing(section, section)
+    }
+
+    /// Indices of every assignment sharing a section with `range`, sorted
+    pub fn overlapping(&self, range: &SectionAssignment<T, S>) -> Vec<usize> {
+        self.sharing(range.start(), range.last())
+    }
+
+    /// Indices of every assignment holding a section from `first` to `last`, sorted
+    fn sharing(&self, first: T, last: T) -> Vec<usize> {
+        let mut found = Vec::new();
+        self.collect_sharing(0, self.ranges.len(), (first, last), &mut found);
+
+        found.sort_unstable();
+        found
+    }
+
+    fn collect_sharing(&self, lo: usize, hi: usize, (first, last): (T, T), found: &mut Vec<usize>) {
+        if lo >= hi {
+            return;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        // Nothing in this subtree reaches the query
+        if self.max_last[mid] < first {
+            return;
+        }
+
+        self.collect_sharing(lo, mid, (first, last), found);
+
+        // Everything from here on starts after the query ends
+        let range = &self.ranges[mid];
+        if range.start() > last {
+            return;
+        }
+        if range.last() >= first {
+            found.push(self.ids[mid]);
+        }
+
+        self.collect_sharing(mid + 1, hi, (first, last), found);
+    }
+
+    /// Every pair of overlapping assignments, as sorted `(lower, higher)` index pairs.
+    ///
+    /// Since the assignments are sorted by start, the ones overlapping an assignment from
+    /// later in the order are exactly those starting before it ends.
+    pub fn overlapping_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for (i, range) in self.ranges.iter().enumerate() {
+            for (j, other) in self.ranges.iter().enumerate().skip(i + 1) {
+                if other.start() > range.last() {
+                    break;
+                }
+
+                let (a, b) = (self.ids[i], self.ids[j]);
+                pairs.push((a.min(b), a.max(b)));
+            }
+        }
+
+        pairs.sort_unstable();
+        pairs
+    }
+
+    /// Number of overlapping pairs, without listing them
+    pub fn overlapping_pair_count(&self) -> usize {
+        self.ranges
+            .iter()
+            .enumerate()
+            .map(|(i, range)| {
+                let later = &self.ranges[i + 1..];
+                later.partition_point(|other| other.start() <= range.last())
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{AssignmentSet, HalfOpen};
+
+    fn index(input: &str) -> IntervalIndex {
+        IntervalIndex::new(
+            input
+                .parse::<AssignmentSet>()
+                .unwrap()
+                .ranges()
+                .iter()
+                .copied(),
+        )
+    }
+
+    #[test]
+    fn point_and_range_queries() {
+        let index = index("5-9,1-3,2-6,8-8,12-20");
+
+        assert_eq!(vec![0, 2], index.containing(5));
+        assert_eq!(vec![1, 2], index.containing(2));
+        assert!(index.containing(10).is_empty());
+        assert!(index.containing(0).is_empty());
+
+        assert_eq!(
+            vec![0, 3, 4],
+            index.overlapping(&SectionAssignment::new(7, 12).unwrap())
+        );
+        assert!(IntervalIndex::<usize>::default().containing(1).is_empty());
+    }
+
+    #[test]
+    fn any_integer() {
+        let index = IntervalIndex::new(
+            "-9..-3,-4..2,2..3,-20..-9"
+                .parse::<AssignmentSet<i16, HalfOpen>>()
+                .unwrap()
+                .ranges()
+                .iter()
+                .copied(),
+        );
+
+        assert_eq!(vec![0, 1], index.containing(-4));
+        assert_eq!(vec![2], index.containing(2));
+        assert!(index.containing(-9).contains(&0));
+        assert!(!index.containing(-9).contains(&3));
+        assert_eq!(vec![(0, 1)], index.overlapping_pairs());
+    }
+
+    #[test]
+    fn all_pairs() {
+        let index = index("5-9,1-3,2-6,8-8,12-20");
+
+        assert_eq!(vec![(0, 2), (0, 3), (1, 2)], index.overlapping_pairs());
+        assert_eq!(3, index.overlapping_pair_count());
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = fastrand::Rng::with_seed(23);
+
+        for _ in 0..50 {
+            let assignments: Vec<SectionAssignment> = (0..rng.usize(..60))
+                .map(|_| {
+                    let start = rng.usize(..100);
+                    SectionAssignment::new(start, start + rng.usize(..15)).unwrap()
+                })
+                .collect();
+            let index = IntervalIndex::new(assignments.iter().copied());
+
+            for _ in 0..20 {
+                let start = rng.usize(..120);
+                let query = SectionAssignment::new(start, start + rng.usize(..10)).unwrap();
+                let expected: Vec<usize> = (0..assignments.len())
+                    .filter(|&idx| assignments[idx].overlaps(&query))
+                    .collect();
+                assert_eq!(expected, index.overlapping(&query));
+            }
+
+            let mut expected = Vec::new();
+            for i in 0..assignments.len() {
+                for j in i + 1..assignments.len() {
+                    if assignments[i].overlaps(&assignments[j]) {
+                        expected.push((i, j));
+                    }
+                }
+            }
+            assert_eq!(expected, index.overlapping_pairs());
+            assert_eq!(expected.len(), index.overlapping_pair_count());
+        }
+    }
+}
@@ -1,56 +1,366 @@
-#[derive(Debug)]
-pub struct SectionAssignment {
-    start: usize,
-    end: usize,
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    marker::PhantomData,
+    num::IntErrorKind,
+    str::{FromStr, Lines},
+};
+
+mod assignment_set;
+mod bounds;
+mod coverage;
+mod interval_index;
+mod render;
+
+pub use assignment_set::AssignmentSet;
+pub use bounds::{Closed, HalfOpen, SectionId, Semantics};
+pub use coverage::{CoverageMap, Segment};
+pub use interval_index::IntervalIndex;
+pub use render::{terminal_width, Highlight, Renderer};
+
+/// Why a line of the section assignment list couldn't be parsed
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseAssignmentErrorKind {
+    /// A range had no separator, such as '-', between its bounds
+    MissingSeparator {
+        range: String,
+        separator: &'static str,
+    },
+    /// The line had no ',' between its two ranges
+    MissingComma,
+    /// The line had more than two ranges
+    TooManyRanges(usize),
+    /// A bound wasn't a whole number
+    NotANumber(String),
+    /// A bound was too large or too small to store
+    Overflow(String),
+    /// The range holds no sections
+    Reversed { start: String, end: String },
 }
 
-impl SectionAssignment {
-    pub fn from_string(input: &str) -> Self {
-        let splits = input.split('-').collect::<Vec<&str>>();
-        let start = splits[0].parse().unwrap();
-        let end = splits[1].parse().unwrap();
+/// A bad section assignment along with where it was found
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseAssignmentError {
+    /// 1-based line number of the offending line
+    pub line: usize,
+    pub kind: ParseAssignmentErrorKind,
+}
 
-        SectionAssignment { start, end }
+impl ParseAssignmentError {
+    fn new(kind: ParseAssignmentErrorKind) -> Self {
+        ParseAssignmentError { line: 1, kind }
     }
 
-    pub fn pair_from_line(input: &str) -> [Self; 2] {
-        let splits = input.split(',').collect::<Vec<&str>>();
+    fn at_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+}
+
+impl Display for ParseAssignmentError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "line {}: ", self.line)?;
+
+        match &self.kind {
+            ParseAssignmentErrorKind::MissingSeparator { range, separator } => {
+                write!(f, "range '{range}' has no '{separator}'")
+            }
+            ParseAssignmentErrorKind::MissingComma => write!(f, "expected two ranges split by ','"),
+            ParseAssignmentErrorKind::TooManyRanges(count) => {
+                write!(f, "expected two ranges, found {count}")
+            }
+            ParseAssignmentErrorKind::NotANumber(bound) => {
+                write!(f, "bound '{bound}' isn't a whole number")
+            }
+            ParseAssignmentErrorKind::Overflow(bound) => {
+                write!(f, "bound '{bound}' is out of range")
+            }
+            ParseAssignmentErrorKind::Reversed { start, end } => {
+                write!(f, "range from {start} to {end} holds no sections")
+            }
+        }
+    }
+}
+
+impl Error for ParseAssignmentError {}
+
+fn parse_bound<T: SectionId>(bound: &str) -> Result<T, ParseAssignmentError> {
+    T::parse_id(bound).map_err(|err| {
+        let kind = match err.kind() {
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+                ParseAssignmentErrorKind::Overflow(bound.to_string())
+            }
+            _ => ParseAssignmentErrorKind::NotANumber(bound.to_string()),
+        };
+
+        ParseAssignmentError::new(kind)
+    })
+}
 
-        [
-            SectionAssignment::from_string(splits[0]),
-            SectionAssignment::from_string(splits[1]),
-        ]
+/// A range of section IDs. Ordered by start, then by end.
+///
+/// `T` is the ID type and `S` decides whether `end` is the final section (`Closed`, the
+/// puzzle's convention) or the first one past it (`HalfOpen`). Either way, every range holds
+/// at least one section.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct SectionAssignment<T = usize, S = Closed> {
+    start: T,
+    end: T,
+    semantics: PhantomData<S>,
+}
+
+impl<T: SectionId, S: Semantics> Display for SectionAssignment<T, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}{}{}", self.start, S::SEPARATOR, self.end)
+    }
+}
+
+impl<T: SectionId, S: Semantics> FromStr for SectionAssignment<T, S> {
+    type Err = ParseAssignmentError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        // A leading '-' on a signed start is its sign, not the separator
+        let skip = usize::from(T::SIGNED && input.starts_with('-'));
+        let split = input[skip..]
+            .find(S::SEPARATOR)
+            .map(|idx| skip + idx)
+            .ok_or_else(|| {
+                ParseAssignmentError::new(ParseAssignmentErrorKind::MissingSeparator {
+                    range: input.to_string(),
+                    separator: S::SEPARATOR,
+                })
+            })?;
+        let start: T = parse_bound(&input[..split])?;
+        let end = parse_bound(&input[split + S::SEPARATOR.len()..])?;
+
+        SectionAssignment::with_bounds(start, end).ok_or_else(|| {
+            ParseAssignmentError::new(ParseAssignmentErrorKind::Reversed {
+                start: start.to_string(),
+                end: end.to_string(),
+            })
+        })
+    }
+}
+
+/// The two assignments on a single line of input
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct AssignmentPair<T = usize, S = Closed>(pub [SectionAssignment<T, S>; 2]);
+
+impl<T: SectionId, S: Semantics> FromStr for AssignmentPair<T, S> {
+    type Err = ParseAssignmentError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let ranges: Vec<&str> = input.split(',').collect();
+
+        match ranges[..] {
+            [first, second] => Ok(AssignmentPair([first.parse()?, second.parse()?])),
+            [_] => Err(ParseAssignmentError::new(
+                ParseAssignmentErrorKind::MissingComma,
+            )),
+            _ => Err(ParseAssignmentError::new(
+                ParseAssignmentErrorKind::TooManyRanges(ranges.len()),
+            )),
+        }
+    }
+}
+
+impl<T: SectionId> SectionAssignment<T, Closed> {
+    /// Creates the range `start..=end`, or `None` if it ends before it starts
+    pub fn new(start: T, end: T) -> Option<Self> {
+        SectionAssignment::with_bounds(start, end)
+    }
+}
+
+impl<T: SectionId> SectionAssignment<T, HalfOpen> {
+    /// Creates the range `start..end`, or `None` if it doesn't end after it starts
+    pub fn half_open(start: T, end: T) -> Option<Self> {
+        SectionAssignment::with_bounds(start, end)
+    }
+}
+
+impl<T: SectionId, S: Semantics> SectionAssignment<T, S> {
+    /// Creates the range with these bounds under `S`, or `None` if it holds no sections
+    pub fn with_bounds(start: T, end: T) -> Option<Self> {
+        S::last(start, end).map(|_| SectionAssignment {
+            start,
+            end,
+            semantics: PhantomData,
+        })
+    }
+
+    /// Creates the range from `start` to `last` inclusive, if `S` can write it
+    fn from_last(start: T, last: T) -> Option<Self> {
+        if start > last {
+            return None;
+        }
+
+        S::end_for(last).map(|end| SectionAssignment {
+            start,
+            end,
+            semantics: PhantomData,
+        })
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    /// The end as written, which is only a section of the range when `S` is `Closed`
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    /// The final section of the range
+    pub fn last(&self) -> T {
+        S::last(self.start, self.end).expect("ranges always hold a section")
+    }
+
+    /// Number of sections in the range. This is a `u128` since `0-usize::MAX` holds one more
+    /// section than `usize` can count, and it saturates for a range over every 128-bit ID.
+    pub fn len(&self) -> u128 {
+        self.last().offset_from(self.start).saturating_add(1)
+    }
+
+    /// Always false, since a range holds at least one section
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The same sections under other semantics, or `None` if they can't be written that way
+    pub fn with_semantics<U: Semantics>(&self) -> Option<SectionAssignment<T, U>> {
+        SectionAssignment::from_last(self.start, self.last())
+    }
+
+    pub fn contains(&self, section: T) -> bool {
+        self.start <= section && section <= self.last()
+    }
+
+    /// Whether every section of `other` is also in this range
+    pub fn contains_range(&self, other: &Self) -> bool {
+        self.start <= other.start && other.last() <= self.last()
+    }
+
+    /// Whether the ranges share at least one section
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.last() && other.start <= self.last()
+    }
+
+    /// Whether the ranges don't overlap but have no gap between them
+    pub fn is_adjacent(&self, other: &Self) -> bool {
+        self.last().next() == Some(other.start) || other.last().next() == Some(self.start)
+    }
+
+    /// The sections in both ranges
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Self::from_last(self.start.max(other.start), self.last().min(other.last()))
+    }
+
+    /// The smallest range holding both ranges, including any gap between them
+    pub fn hull(&self, other: &Self) -> Self {
+        Self::from_last(self.start.min(other.start), self.last().max(other.last()))
+            .expect("the furthest last section is already written as an end")
+    }
+
+    /// The sections in either range, or `None` if there's a gap so they can't form one range
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        (self.overlaps(other) || self.is_adjacent(other)).then(|| self.hull(other))
+    }
+
+    /// The sections in this range but not in `other`, as the pieces below and above `other`
+    pub fn difference(&self, other: &Self) -> (Option<Self>, Option<Self>) {
+        if !self.overlaps(other) {
+            return (Some(*self), None);
+        }
+
+        let below = other
+            .start
+            .prev()
+            .and_then(|last| Self::from_last(self.start, last));
+        let above = other
+            .last()
+            .next()
+            .and_then(|start| Self::from_last(start, self.last()));
+
+        (below, above)
+    }
+
+    /// Whether one of the assignments contains all of the others
+    pub fn has_full_overlap(assignments: &[Self]) -> bool {
+        let hull = assignments.iter().copied().reduce(|a, b| a.hull(&b));
+        hull.is_some_and(|hull| assignments.contains(&hull))
+    }
+
+    /// Whether any two of the assignments share a section
+    pub fn has_partial_overlap(assignments: &[Self]) -> bool {
+        assignments
+            .iter()
+            .enumerate()
+            .any(|(i, a)| assignments[i + 1..].iter().any(|b| a.overlaps(b)))
+    }
+
+    /// Parses every line, reporting all of the bad ones rather than just the first
+    pub fn pairs_from_lines(lines: Lines) -> Result<Vec<[Self; 2]>, Vec<ParseAssignmentError>> {
+        parse_lines(lines).map(|pairs| {
+            pairs
+                .into_iter()
+                .map(|pair: AssignmentPair<T, S>| pair.0)
+                .collect()
+        })
+    }
+
+    /// Parses every line as a crew of any size, reporting all of the bad lines
+    pub fn sets_from_lines(
+        lines: Lines,
+    ) -> Result<Vec<AssignmentSet<T, S>>, Vec<ParseAssignmentError>> {
+        parse_lines(lines)
+    }
+}
+
+impl SectionAssignment {
+    /// Panics on malformed input. Use `str::parse` to handle bad input gracefully.
+    pub fn from_string(input: &str) -> Self {
+        match input.parse() {
+            Ok(assignment) => assignment,
+            Err(err) => panic!("Failed to parse section assignment: {err}"),
+        }
     }
 
-    pub fn has_full_overlap(pair: &[SectionAssignment; 2]) -> bool {
-        if pair[0].start >= pair[1].start && pair[0].end <= pair[1].end {
-            true
-        } else if pair[1].start >= pair[0].start && pair[1].end <= pair[0].end {
-            true
-        } else {
-            false
+    /// Panics on malformed input. Use `str::parse` to handle bad input gracefully.
+    pub fn pair_from_line(input: &str) -> [Self; 2] {
+        match input.parse::<AssignmentPair>() {
+            Ok(pair) => pair.0,
+            Err(err) => panic!("Failed to parse section assignments: {err}"),
         }
     }
+}
 
-    pub fn has_partial_overlap(pair: &[SectionAssignment; 2]) -> bool {
-        if pair[0].start >= pair[1].start && pair[0].start <= pair[1].end {
-            true
-        } else if pair[1].start >= pair[0].start && pair[1].start <= pair[0].end {
-            true
-        } else if pair[0].end <= pair[1].end && pair[0].end >= pair[1].start {
-            true
-        } else if pair[1].end <= pair[0].end && pair[1].end >= pair[0].start {
-            true
-        } else {
-            false
+fn parse_lines<T>(lines: Lines) -> Result<Vec<T>, Vec<ParseAssignmentError>>
+where
+    T: FromStr<Err = ParseAssignmentError>,
+{
+    let mut parsed = Vec::new();
+    let mut errors = Vec::new();
+
+    for (idx, line) in lines.enumerate() {
+        match line.parse::<T>() {
+            Ok(value) => parsed.push(value),
+            Err(err) => errors.push(err.at_line(idx + 1)),
         }
     }
+
+    if errors.is_empty() {
+        Ok(parsed)
+    } else {
+        Err(errors)
+    }
 }
 
 #[cfg(test)]
 mod test {
     mod section_assignment {
-        use crate::SectionAssignment;
+        use crate::{
+            AssignmentPair, ParseAssignmentError, ParseAssignmentErrorKind, SectionAssignment,
+        };
 
         #[test]
         fn create_from_string() {
@@ -64,6 +374,7 @@ mod test {
         }
 
         #[test]
+        #[allow(clippy::needless_range_loop)]
         fn create_pair_from_string() {
             let input_data = [("2-4,6-8", [(2, 4), (6, 8)])];
 
@@ -108,5 +419,406 @@ mod test {
                 );
             }
         }
+
+        #[test]
+        fn parse_errors() {
+            let input_data = [
+                ("2-4", ParseAssignmentErrorKind::MissingComma),
+                ("", ParseAssignmentErrorKind::MissingComma),
+                (
+                    "2-4,68",
+                    ParseAssignmentErrorKind::MissingSeparator {
+                        range: "68".to_string(),
+                        separator: "-",
+                    },
+                ),
+                (
+                    "2-x,6-8",
+                    ParseAssignmentErrorKind::NotANumber("x".to_string()),
+                ),
+                (
+                    "2-4,-8",
+                    ParseAssignmentErrorKind::NotANumber("".to_string()),
+                ),
+                ("2-4,6-8,1-2", ParseAssignmentErrorKind::TooManyRanges(3)),
+                ("2-4,x,", ParseAssignmentErrorKind::TooManyRanges(3)),
+                (
+                    "4-2,6-8",
+                    ParseAssignmentErrorKind::Reversed {
+                        start: "4".to_string(),
+                        end: "2".to_string(),
+                    },
+                ),
+                (
+                    "2-4,6-99999999999999999999",
+                    ParseAssignmentErrorKind::Overflow("99999999999999999999".to_string()),
+                ),
+            ];
+
+            for data in input_data {
+                assert_eq!(
+                    Err(ParseAssignmentError {
+                        line: 1,
+                        kind: data.1
+                    }),
+                    data.0.parse::<AssignmentPair>(),
+                    "{:?}",
+                    data.0
+                );
+            }
+
+            assert_eq!(
+                Ok(AssignmentPair([
+                    SectionAssignment::new(3, 3).unwrap(),
+                    SectionAssignment::new(1, 9).unwrap()
+                ])),
+                "3-3,1-9".parse()
+            );
+        }
+
+        #[test]
+        fn every_bad_line_reported() {
+            let input = "2-4,6-8\n2-4\n5-7,7-9\n9-1,1-2\n";
+
+            let errors = SectionAssignment::<usize>::pairs_from_lines(input.lines()).unwrap_err();
+            assert_eq!(
+                vec![
+                    (2, ParseAssignmentErrorKind::MissingComma),
+                    (
+                        4,
+                        ParseAssignmentErrorKind::Reversed {
+                            start: "9".to_string(),
+                            end: "1".to_string(),
+                        }
+                    )
+                ],
+                errors
+                    .into_iter()
+                    .map(|err| (err.line, err.kind))
+                    .collect::<Vec<_>>()
+            );
+
+            let pairs =
+                SectionAssignment::<usize>::pairs_from_lines("2-4,6-8\n5-7,7-9".lines()).unwrap();
+            assert_eq!(2, pairs.len());
+        }
+
+        fn range(input: &str) -> SectionAssignment {
+            SectionAssignment::from_string(input)
+        }
+
+        #[test]
+        fn bounds() {
+            assert_eq!(Some(range("3-7")), SectionAssignment::new(3, 7));
+            assert_eq!(None, SectionAssignment::new(7, 3));
+
+            let sections = range("3-7");
+            assert_eq!(5, sections.len());
+            assert_eq!(1, range("4-4").len());
+            assert_eq!(
+                1 << usize::BITS,
+                SectionAssignment::new(0, usize::MAX).unwrap().len()
+            );
+            assert!(sections.contains(3) && sections.contains(7));
+            assert!(!sections.contains(2) && !sections.contains(8));
+            assert_eq!("3-7", sections.to_string());
+        }
+
+        #[test]
+        fn intersection_and_union() {
+            assert_eq!(Some(range("5-6")), range("2-6").intersection(&range("5-9")));
+            assert_eq!(Some(range("7-7")), range("5-7").intersection(&range("7-9")));
+            assert_eq!(None, range("2-4").intersection(&range("6-8")));
+
+            assert_eq!(range("2-8"), range("2-4").hull(&range("6-8")));
+            assert_eq!(None, range("2-4").union(&range("6-8")));
+            assert_eq!(Some(range("2-8")), range("2-5").union(&range("6-8")));
+            assert_eq!(Some(range("1-9")), range("4-8").union(&range("1-9")));
+        }
+
+        #[test]
+        fn difference() {
+            assert_eq!(
+                (Some(range("1-3")), Some(range("7-9"))),
+                range("1-9").difference(&range("4-6"))
+            );
+            assert_eq!(
+                (None, Some(range("7-9"))),
+                range("4-9").difference(&range("1-6"))
+            );
+            assert_eq!(
+                (Some(range("1-3")), None),
+                range("1-6").difference(&range("4-9"))
+            );
+            assert_eq!((None, None), range("4-6").difference(&range("1-9")));
+            assert_eq!(
+                (Some(range("1-3")), None),
+                range("1-3").difference(&range("5-9"))
+            );
+            assert_eq!(
+                (Some(range("1-3")), None),
+                range("1-3").difference(&SectionAssignment::new(4, usize::MAX).unwrap())
+            );
+            assert_eq!(
+                (None, Some(range("1-3"))),
+                range("0-3").difference(&range("0-0"))
+            );
+        }
+
+        #[test]
+        fn containment_and_adjacency() {
+            assert!(range("1-9").contains_range(&range("4-8")));
+            assert!(range("4-8").contains_range(&range("4-8")));
+            assert!(!range("4-8").contains_range(&range("1-9")));
+
+            assert!(range("2-4").is_adjacent(&range("5-8")));
+            assert!(range("5-8").is_adjacent(&range("2-4")));
+            assert!(!range("2-4").is_adjacent(&range("4-8")));
+            assert!(!range("2-4").is_adjacent(&range("6-8")));
+
+            let max = SectionAssignment::new(usize::MAX, usize::MAX).unwrap();
+            assert!(!max.is_adjacent(&range("0-0")));
+        }
+
+        #[test]
+        fn ordering() {
+            let mut ranges = vec![range("4-8"), range("1-9"), range("4-5"), range("2-2")];
+            ranges.sort();
+
+            assert_eq!(
+                vec![range("1-9"), range("2-2"), range("4-5"), range("4-8")],
+                ranges
+            );
+        }
+
+        #[test]
+        fn crew_overlaps() {
+            let crews = SectionAssignment::<usize>::sets_from_lines(
+                "2-4,6-8,3-3\n1-9,4-8,2-2\n1-2,3-4,5-6".lines(),
+            )
+            .unwrap();
+            let full: Vec<bool> = crews
+                .iter()
+                .map(|crew| SectionAssignment::has_full_overlap(crew.ranges()))
+                .collect();
+            let partial: Vec<bool> = crews
+                .iter()
+                .map(|crew| SectionAssignment::has_partial_overlap(crew.ranges()))
+                .collect();
+
+            assert_eq!(vec![false, true, false], full);
+            assert_eq!(vec![true, true, false], partial);
+        }
+    }
+
+    mod semantics {
+        use crate::{
+            Closed, HalfOpen, ParseAssignmentErrorKind, SectionAssignment, SectionId, Semantics,
+        };
+
+        /// Bounds near zero and both extremes of `i8`
+        const BOUNDS: [i8; 11] = [-128, -127, -126, -2, -1, 0, 1, 2, 125, 126, 127];
+
+        fn closed_ranges() -> Vec<SectionAssignment<i8>> {
+            let mut ranges = Vec::new();
+            for (i, &start) in BOUNDS.iter().enumerate() {
+                for &end in &BOUNDS[i..] {
+                    ranges.push(SectionAssignment::new(start, end).unwrap());
+                }
+            }
+
+            ranges
+        }
+
+        /// Every closed range that can also be written half-open, alongside its equivalent
+        fn equivalent_ranges() -> Vec<(SectionAssignment<i8>, SectionAssignment<i8, HalfOpen>)> {
+            closed_ranges()
+                .into_iter()
+                .filter_map(|range| Some((range, range.with_semantics()?)))
+                .collect()
+        }
+
+        fn sections<T: SectionId, S: Semantics>(
+            range: Option<SectionAssignment<T, S>>,
+        ) -> Option<(T, T)> {
+            range.map(|range| (range.start(), range.last()))
+        }
+
+        #[test]
+        fn conversions() {
+            for range in closed_ranges() {
+                let half_open = range.with_semantics::<HalfOpen>();
+                assert_eq!(range.end() == i8::MAX, half_open.is_none(), "{range}");
+
+                if let Some(half_open) = half_open {
+                    assert_eq!(range.end() + 1, half_open.end());
+                    assert_eq!(range.last(), half_open.last());
+                    assert_eq!(Some(range), half_open.with_semantics::<Closed>());
+                }
+            }
+
+            assert_eq!(None, SectionAssignment::half_open(3, 3));
+            assert_eq!(None, SectionAssignment::half_open(4u8, 3));
+            assert_eq!(
+                SectionAssignment::new(u128::MAX - 1, u128::MAX - 1),
+                SectionAssignment::half_open(u128::MAX - 1, u128::MAX)
+                    .unwrap()
+                    .with_semantics()
+            );
+        }
+
+        #[test]
+        fn pairs_match_sections() {
+            // Compare each operation against the sections of every i8
+            let ranges = equivalent_ranges();
+
+            for (a, half_a) in &ranges {
+                for (b, half_b) in &ranges {
+                    let shared = (-128..=127).any(|id| a.contains(id) && b.contains(id));
+                    let within = (-128..=127).all(|id| !b.contains(id) || a.contains(id));
+
+                    assert_eq!(shared, a.overlaps(b));
+                    assert_eq!(shared, half_a.overlaps(half_b));
+                    assert_eq!(within, a.contains_range(b));
+                    assert_eq!(within, half_a.contains_range(half_b));
+                    assert_eq!(a.is_adjacent(b), half_a.is_adjacent(half_b));
+
+                    assert_eq!(
+                        sections(a.intersection(b)),
+                        sections(half_a.intersection(half_b))
+                    );
+                    assert_eq!(sections(a.union(b)), sections(half_a.union(half_b)));
+                    assert_eq!(
+                        sections(Some(a.hull(b))),
+                        sections(Some(half_a.hull(half_b)))
+                    );
+
+                    let (below, above) = a.difference(b);
+                    let (half_below, half_above) = half_a.difference(half_b);
+                    assert_eq!(sections(below), sections(half_below));
+                    assert_eq!(sections(above), sections(half_above));
+                }
+            }
+        }
+
+        #[test]
+        fn overlaps_match_across_semantics() {
+            // Every pair and triple of ranges that both conventions can write
+            let ranges = equivalent_ranges();
+
+            for (a, half_a) in &ranges {
+                for (b, half_b) in &ranges {
+                    let closed = [*a, *b];
+                    let half_open = [*half_a, *half_b];
+                    assert_eq!(
+                        SectionAssignment::has_full_overlap(&closed),
+                        SectionAssignment::has_full_overlap(&half_open)
+                    );
+                    assert_eq!(
+                        SectionAssignment::has_partial_overlap(&closed),
+                        SectionAssignment::has_partial_overlap(&half_open)
+                    );
+
+                    for (c, half_c) in &ranges {
+                        let closed = [*a, *b, *c];
+                        let half_open = [*half_a, *half_b, *half_c];
+                        assert_eq!(
+                            SectionAssignment::has_full_overlap(&closed),
+                            SectionAssignment::has_full_overlap(&half_open)
+                        );
+                        assert_eq!(
+                            SectionAssignment::has_partial_overlap(&closed),
+                            SectionAssignment::has_partial_overlap(&half_open)
+                        );
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn len_of_any_integer() {
+            assert_eq!(256, SectionAssignment::new(i8::MIN, i8::MAX).unwrap().len());
+            assert_eq!(3, SectionAssignment::new(-1i64, 1).unwrap().len());
+            assert_eq!(5, SectionAssignment::half_open(-2i32, 3).unwrap().len());
+            assert_eq!(
+                u128::MAX,
+                SectionAssignment::new(1, u128::MAX).unwrap().len()
+            );
+            // Every 128-bit ID is one more than a u128 can count
+            assert_eq!(
+                u128::MAX,
+                SectionAssignment::new(i128::MIN, i128::MAX).unwrap().len()
+            );
+        }
+
+        #[test]
+        fn parse_any_lines() {
+            let crews = SectionAssignment::<i32, HalfOpen>::sets_from_lines(
+                "-5..-2,-3..0\n0..4,1..2,3..9".lines(),
+            )
+            .unwrap();
+            assert_eq!(2, crews.len());
+            assert!(SectionAssignment::has_partial_overlap(crews[0].ranges()));
+            assert!(!SectionAssignment::has_full_overlap(crews[1].ranges()));
+
+            let pairs = SectionAssignment::<u128>::pairs_from_lines(
+                format!("0-{max},{max}-{max}", max = u128::MAX).lines(),
+            )
+            .unwrap();
+            assert!(SectionAssignment::has_full_overlap(&pairs[0]));
+
+            let errors = SectionAssignment::<i8>::pairs_from_lines("1-2,3-4\n-1-200,1-2".lines())
+                .unwrap_err();
+            assert_eq!(2, errors[0].line);
+            assert_eq!(
+                ParseAssignmentErrorKind::Overflow("200".to_string()),
+                errors[0].kind
+            );
+        }
+
+        #[test]
+        fn parse_any_integer() {
+            assert_eq!(
+                SectionAssignment::new(-5, -3),
+                "-5--3".parse::<SectionAssignment<i32>>().ok()
+            );
+            assert_eq!(SectionAssignment::half_open(-3i64, 2), "-3..2".parse().ok());
+            assert_eq!(
+                SectionAssignment::new(0, u128::MAX),
+                format!("0-{}", u128::MAX).parse().ok()
+            );
+            assert_eq!(
+                "2..5",
+                "2..5"
+                    .parse::<SectionAssignment<u8, HalfOpen>>()
+                    .unwrap()
+                    .to_string()
+            );
+
+            let error = |input: &str| {
+                input
+                    .parse::<SectionAssignment<i8, HalfOpen>>()
+                    .unwrap_err()
+                    .kind
+            };
+            assert_eq!(
+                ParseAssignmentErrorKind::Reversed {
+                    start: "4".to_string(),
+                    end: "4".to_string()
+                },
+                error("4..4")
+            );
+            assert_eq!(
+                ParseAssignmentErrorKind::Overflow("-129".to_string()),
+                error("-129..0")
+            );
+            assert_eq!(
+                ParseAssignmentErrorKind::MissingSeparator {
+                    range: "2-5".to_string(),
+                    separator: ".."
+                },
+                error("2-5")
+            );
+        }
     }
 }
@@ -1,25 +1,124 @@
-use camp_cleanup::SectionAssignment;
-use std::fs;
+use camp_cleanup::{terminal_width, CoverageMap, Highlight, Renderer, SectionAssignment};
+use clap::{Arg, ArgAction, Command};
+use std::{fs, io, process::ExitCode};
 
-fn main() {
-    let input_data = fs::read_to_string("input.txt").expect("Failed to read input file");
-
-    let mut assignment_groups = Vec::new();
-    for line in input_data.lines() {
-        assignment_groups.push(SectionAssignment::pair_from_line(line));
+/// Read the input file, or stdin when no path (or `-`) is given
+fn read_input(path: Option<&String>) -> io::Result<String> {
+    match path.map(String::as_str) {
+        None | Some("-") => io::read_to_string(io::stdin()),
+        Some(path) => fs::read_to_string(path),
     }
+}
+
+fn parse_line_range(lines: &str) -> Result<SectionAssignment, String> {
+    lines
+        .parse()
+        .map_err(|_| format!("expected a line range like 3-5, not '{lines}'"))
+}
+
+fn parse_window(sections: &str) -> Result<SectionAssignment, String> {
+    sections
+        .parse()
+        .map_err(|_| format!("expected a section range like 10-40, not '{sections}'"))
+}
+
+fn main() -> ExitCode {
+    let cfg = Command::new("Camp Cleanup Overlap Finder")
+        .author("Brenden Davidson")
+        .version("0.1.0")
+        .about("Advent of Code 2022: Day 4 solution")
+        .args([
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .action(ArgAction::Set)
+                .help("the input file to use, reads stdin when missing or '-'"),
+            Arg::new("render")
+                .short('r')
+                .long("render")
+                .action(ArgAction::Set)
+                .value_name("LINES")
+                .value_parser(parse_line_range)
+                .help("draw the crews on these input lines, e.g. 3-5"),
+            Arg::new("window")
+                .short('w')
+                .long("window")
+                .action(ArgAction::Set)
+                .value_name("SECTIONS")
+                .value_parser(parse_window)
+                .requires("render")
+                .help("only draw these sections rather than scaling to fit, e.g. 10-40"),
+        ])
+        .get_matches();
+
+    let input_path = cfg.get_one::<String>("input");
+    let render_lines = cfg.get_one::<SectionAssignment>("render").copied();
+    let window = cfg.get_one::<SectionAssignment>("window").copied();
+
+    let input_data = match read_input(input_path) {
+        Ok(input_data) => input_data,
+        Err(err) => {
+            eprintln!(
+                "error: failed to read '{}': {err}",
+                input_path.map_or("-", String::as_str)
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let assignment_groups = match SectionAssignment::<usize>::sets_from_lines(input_data.lines()) {
+        Ok(pairs) => pairs,
+        Err(errors) => {
+            for err in errors {
+                eprintln!("error: {err}");
+            }
+            return ExitCode::FAILURE;
+        }
+    };
 
     let mut full_overlap = 0;
     let mut partial_overlap = 0;
-    for pair in assignment_groups {
-        if SectionAssignment::has_full_overlap(&pair) {
+    for crew in &assignment_groups {
+        if SectionAssignment::has_full_overlap(crew.ranges()) {
             full_overlap += 1;
         }
-        if SectionAssignment::has_partial_overlap(&pair) {
+        if SectionAssignment::has_partial_overlap(crew.ranges()) {
             partial_overlap += 1;
         }
     }
 
     println!("Full overlap: {}", full_overlap);
     println!("Partial overlap: {}", partial_overlap);
+
+    let coverage = CoverageMap::new(
+        assignment_groups
+            .iter()
+            .flat_map(|crew| crew.ranges().iter().copied()),
+    );
+    println!("Sections covered: {}", coverage.covered_sections());
+    println!("Uncovered gaps: {}", coverage.gaps().len());
+    println!("Redundant sections: {}", coverage.redundant_sections());
+    println!(
+        "Minimum cover: {} of {} assignments",
+        coverage.minimum_cover().len(),
+        coverage.assignments().len()
+    );
+
+    if let Some(lines) = render_lines {
+        let mut renderer = Renderer::new()
+            .width(terminal_width())
+            .highlight(Highlight::detect());
+        if let Some(window) = window {
+            renderer = renderer.window(window);
+        }
+
+        for (idx, crew) in assignment_groups.iter().enumerate() {
+            if lines.contains(idx + 1) {
+                println!("\nLine {}:", idx + 1);
+                print!("{}", renderer.render(crew.ranges()));
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
 }
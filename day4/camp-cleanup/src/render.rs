@@ -0,0 +1,234 @@
+use std::{env, fmt::Write, io::IsTerminal};
+
+use terminal_size::{terminal_size, Width};
+
+use crate::{coverage::sweep, Closed, SectionAssignment, SectionId, Semantics};
+
+const OVERLAP_COLOUR: &str = "\x1b[1;33m";
+const RESET: &str = "\x1b[0m";
+
+/// How sections cleaned by more than one elf stand out
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Highlight {
+    /// ANSI colour on the overlapping cells
+    Colour,
+    /// An extra row of `^` under the overlapping cells
+    Markers,
+}
+
+impl Highlight {
+    /// Colour when stdout is a terminal and `NO_COLOR` isn't set, markers otherwise
+    pub fn detect() -> Self {
+        if std::io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none() {
+            Highlight::Colour
+        } else {
+            Highlight::Markers
+        }
+    }
+}
+
+/// Width of the terminal on stdout, falling back to `COLUMNS` and then to 80.
+///
+/// Shells don't usually export `COLUMNS`, so the terminal itself is asked first.
+pub fn terminal_width() -> usize {
+    terminal_size()
+        .map(|(Width(columns), _)| usize::from(columns))
+        .or_else(|| env::var("COLUMNS").ok()?.parse().ok())
+        .filter(|&columns| columns > 0)
+        .unwrap_or(80)
+}
+
+/// Draws assignments the way the puzzle does, one `.234.....  2-4` row each.
+///
+/// Views wider than the line are scaled down so each cell stands for several sections,
+/// drawn as `#` wherever the row cleans any of them.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Renderer<T = usize, S = Closed> {
+    width: usize,
+    highlight: Highlight,
+    window: Option<SectionAssignment<T, S>>,
+}
+
+impl<T: SectionId, S: Semantics> Default for Renderer<T, S> {
+    fn default() -> Self {
+        Renderer::new()
+    }
+}
+
+impl<T: SectionId, S: Semantics> Renderer<T, S> {
+    /// An 80 column renderer using markers
+    pub fn new() -> Self {
+        Renderer {
+            width: 80,
+            highlight: Highlight::Markers,
+            window: None,
+        }
+    }
+
+    /// Total line width, including the labels
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn highlight(mut self, highlight: Highlight) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Only draw these sections, rather than everything the assignments span
+    pub fn window(mut self, window: SectionAssignment<T, S>) -> Self {
+        self.window = Some(window);
+        self
+    }
+
+    pub fn render(&self, assignments: &[SectionAssignment<T, S>]) -> String {
+        let view = match self
+            .window
+            .or_else(|| assignments.iter().copied().reduce(|a, b| a.hull(&b)))
+        {
+            Some(view) => view,
+            None => return String::new(),
+        };
+
+        let label_width = assignments
+            .iter()
+            .map(|range| range.to_string().len() + 2)
+            .max()
+            .unwrap_or(0);
+        let columns = self.width.saturating_sub(label_width).max(1) as u128;
+        let span = view.last().offset_from(view.start());
+        let per_cell = (span / columns).saturating_add(1);
+
+        let cells: Vec<SectionAssignment<T, S>> = (0..=span / per_cell)
+            .map(|idx| {
+                let start = view.start().forward(idx * per_cell).unwrap();
+                let last = start
+                    .forward(per_cell - 1)
+                    .map_or(view.last(), |last| last.min(view.last()));
+                SectionAssignment::from_last(start, last).unwrap()
+            })
+            .collect();
+
+        let shared: Vec<SectionAssignment<T, S>> = sweep(assignments)
+            .into_iter()
+            .filter(|segment| segment.count > 1)
+            .map(|segment| segment.range)
+            .collect();
+        let overlapping: Vec<bool> = cells
+            .iter()
+            .map(|cell| shared.iter().any(|range| range.overlaps(cell)))
+            .collect();
+
+        let mut output = String::new();
+        if per_cell > 1 {
+            writeln!(output, "sections {view}, {per_cell} per cell").unwrap();
+        }
+
+        for range in assignments {
+            for cell in &cells {
+                // Only the part of the cell this row cleans counts towards its highlight
+                let cleaned = range.intersection(cell);
+                let symbol = match cleaned {
+                    None => '.',
+                    Some(_) if per_cell > 1 => '#',
+                    // The ones digit, whatever the sign
+                    Some(_) => cell.start().to_string().pop().unwrap(),
+                };
+                let overlap = cleaned
+                    .is_some_and(|cleaned| shared.iter().any(|range| range.overlaps(&cleaned)));
+
+                if overlap && self.highlight == Highlight::Colour {
+                    write!(output, "{OVERLAP_COLOUR}{symbol}{RESET}").unwrap();
+                } else {
+                    output.push(symbol);
+                }
+            }
+            writeln!(output, "  {range}").unwrap();
+        }
+
+        if self.highlight == Highlight::Markers && overlapping.contains(&true) {
+            for &overlap in &overlapping {
+                output.push(if overlap { '^' } else { ' ' });
+            }
+            output.push_str("  overlap\n");
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{AssignmentSet, HalfOpen};
+
+    fn ranges(input: &str) -> Vec<SectionAssignment> {
+        input.parse::<AssignmentSet>().unwrap().ranges().to_vec()
+    }
+
+    #[test]
+    fn puzzle_rows() {
+        let renderer = Renderer::new().window(SectionAssignment::new(1, 9).unwrap());
+
+        assert_eq!(
+            ".234.....  2-4\n.....678.  6-8\n",
+            renderer.render(&ranges("2-4,6-8"))
+        );
+        assert_eq!(
+            "....567..  5-7\n......789  7-9\n      ^    overlap\n",
+            renderer.render(&ranges("5-7,7-9"))
+        );
+        assert_eq!("", Renderer::<usize>::new().render(&[]));
+    }
+
+    #[test]
+    fn colour_highlight() {
+        let rendered = Renderer::new()
+            .highlight(Highlight::Colour)
+            .render(&ranges("2-4,4-5"));
+
+        assert_eq!(
+            format!("23{OVERLAP_COLOUR}4{RESET}.  2-4\n..{OVERLAP_COLOUR}4{RESET}5  4-5\n"),
+            rendered
+        );
+    }
+
+    #[test]
+    fn scaled_to_width() {
+        let rendered = Renderer::new().width(20).render(&ranges("1-60,50-100"));
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!("sections 1-100, 9 per cell", lines[0]);
+        assert_eq!("#######.....  1-60", lines[1]);
+        assert_eq!(".....#######  50-100", lines[2]);
+        assert_eq!("     ^^       overlap", lines[3]);
+        assert!(lines[1..3].iter().all(|line| line.len() <= 20));
+    }
+
+    #[test]
+    fn scaled_colour_follows_each_row() {
+        // Sections 28-29 are shared, and fall in the last cell alongside 25-26 from the first row
+        let rendered = Renderer::new()
+            .width(11)
+            .highlight(Highlight::Colour)
+            .render(&ranges("1-26,28-29,28-29"));
+        let lines: Vec<&str> = rendered.lines().collect();
+        let shared = format!("...{OVERLAP_COLOUR}#{RESET}  28-29");
+
+        assert_eq!("sections 1-29, 8 per cell", lines[0]);
+        assert_eq!("####  1-26", lines[1]);
+        assert_eq!(shared, lines[2]);
+        assert_eq!(shared, lines[3]);
+    }
+
+    #[test]
+    fn negative_sections() {
+        let crew: AssignmentSet<i32, HalfOpen> = "-3..0,-1..2".parse().unwrap();
+
+        assert_eq!(
+            "321..  -3..0\n..101  -1..2\n  ^    overlap\n",
+            Renderer::new().render(crew.ranges())
+        );
+    }
+}